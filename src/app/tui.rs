@@ -4,7 +4,10 @@ use crossterm::event::{self, Event, KeyCode};
 use ratatui::{prelude::*, widgets::*};
 use tui_input::{backend::crossterm::EventHandler, Input};
 
-use crate::prelude::Game;
+use crate::backend::database::async_store::{AsyncStore, Pending};
+use crate::backend::database::query::GameQuery;
+use crate::backend::database::stats::GameTotals;
+use crate::prelude::{Date, Game};
 
 #[derive(Default, Clone, Copy)]
 #[repr(u8)]
@@ -29,6 +32,36 @@ enum Message {
     Exit,
 }
 
+// Statistics of one panel on the main screen, read in the background so drawing never waits
+#[derive(Default)]
+enum Panel {
+    #[default]
+    NotConnected,
+    Connecting,
+    Loading(Pending<GameTotals>),
+    Loaded(GameTotals),
+    Failed(String),
+}
+
+impl Panel {
+    fn text(&self) -> String {
+        match self {
+            Self::NotConnected => String::from("No store connected"),
+            Self::Connecting => String::from("Connecting..."),
+            Self::Loading(_) => String::from("Loading..."),
+            Self::Loaded(totals) if totals.games() == 0 => String::from("No games"),
+            Self::Loaded(totals) => format!(
+                "Games: {}\nAverage: {:.1}\nStrikes: {:.1}%\nSpares: {:.1}%",
+                totals.games(),
+                totals.average(),
+                totals.strike_rate() * 100.0,
+                totals.spare_rate() * 100.0
+            ),
+            Self::Failed(err) => err.clone(),
+        }
+    }
+}
+
 pub struct App {
     mode: Mode,
     input: Input,
@@ -36,6 +69,11 @@ pub struct App {
     updated: bool,
 
     game: Option<Game>,
+
+    connecting: Option<Pending<AsyncStore>>,
+    store: Option<AsyncStore>,
+    // This month, this year and overall
+    panels: [Panel; 3],
 }
 
 impl Default for App {
@@ -47,15 +85,76 @@ impl Default for App {
             updated: false,
 
             game: None,
+
+            connecting: None,
+            store: None,
+            panels: Default::default(),
+        }
+    }
+}
+
+impl App {
+    // Constructor
+    pub fn build_with(store: Pending<AsyncStore>) -> Self {
+        App {
+            connecting: Some(store),
+            panels: [(); 3].map(|_| Panel::Connecting),
+            ..Self::default()
+        }
+    }
+
+    // Method
+    // Picks up background work that finished since the last frame
+    fn poll(&mut self) {
+        if let Some(opened) = self.connecting.as_mut().and_then(Pending::try_take) {
+            self.connecting = None;
+
+            match opened {
+                Ok(store) => {
+                    self.panels =
+                        panel_queries().map(|query| Panel::Loading(load_totals(&store, query)));
+                    self.store = Some(store);
+                }
+                Err(err) => self.panels = [(); 3].map(|_| Panel::Failed(err.to_string())),
+            }
+        }
+
+        for panel in self.panels.iter_mut() {
+            if let Panel::Loading(pending) = panel {
+                match pending.try_take() {
+                    None => (),
+                    Some(Ok(totals)) => *panel = Panel::Loaded(totals),
+                    Some(Err(err)) => *panel = Panel::Failed(err.to_string()),
+                }
+            }
         }
     }
 }
 
+// Sessions since the start of this month, since the start of this year, and of all time
+fn panel_queries() -> [GameQuery; 3] {
+    let today = Date::build();
+
+    let mut month = GameQuery::build();
+    month.set_from(Some(Date::build_with(today.year(), today.month(), 1)));
+
+    let mut year = GameQuery::build();
+    year.set_from(Some(Date::build_with(today.year(), 1, 1)));
+
+    [month, year, GameQuery::build()]
+}
+
+// Added up by the store, on the server for MongoDB, no-tap games are left out like `Games` does
+fn load_totals(store: &AsyncStore, query: GameQuery) -> Pending<GameTotals> {
+    store.totals_async(&query, false)
+}
+
 pub fn run(app: &mut App) -> io::Result<()> {
     ui::install_panic_hook();
     let mut terminal = ui::init_terminal()?;
 
     while !app.exit {
+        app.poll();
         terminal.draw(|f| ui(f, app))?;
 
         handle_events(app);
@@ -91,18 +190,18 @@ fn ui(f: &mut Frame, app: &App) {
             .split(main_layout[1]);
 
             f.render_widget(title_block, main_layout[0]);
-            f.render_widget(
-                Block::new().borders(Borders::ALL).title("This Month"),
-                sub_layout[0],
-            );
-            f.render_widget(
-                Block::new().borders(Borders::ALL).title("This Year"),
-                sub_layout[1],
-            );
-            f.render_widget(
-                Block::new().borders(Borders::ALL).title("Overall"),
-                sub_layout[2],
-            );
+            for ((panel, title), area) in app
+                .panels
+                .iter()
+                .zip(["This Month", "This Year", "Overall"])
+                .zip(sub_layout.iter())
+            {
+                f.render_widget(
+                    Paragraph::new(panel.text())
+                        .block(Block::new().borders(Borders::ALL).title(title)),
+                    *area,
+                );
+            }
         }
         Mode::NewGame => {
             constraints.push(Constraint::Max(3));
@@ -110,17 +209,21 @@ fn ui(f: &mut Frame, app: &App) {
             let main_layout = main_layout.constraints(constraints).split(f.size());
 
             f.render_widget(title_block, main_layout[0]);
-            if app.game.is_none() {
-                f.render_widget(
+            match &app.game {
+                None => f.render_widget(
                     Paragraph::new("No game selected: Enter Date")
                         .block(Block::new().borders(Borders::ALL).title("New Game")),
                     main_layout[1],
-                )
-            } else {
-                f.render_widget(
-                    Block::new().borders(Borders::ALL).title("New Game"),
+                ),
+                Some(game) => f.render_widget(
+                    Paragraph::new(format!(
+                        "{}\n\nMax Possible: {}",
+                        game.scorecard(),
+                        game.possible_score()
+                    ))
+                    .block(Block::new().borders(Borders::ALL).title("New Game")),
                     main_layout[1],
-                );
+                ),
             }
             f.render_widget(input_block, main_layout[2]);
         }
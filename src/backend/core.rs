@@ -1,4 +1,6 @@
+pub(crate) mod scoring;
 pub(crate) mod types;
+pub(crate) mod variant;
 
 #[cfg(test)]
 mod tests;
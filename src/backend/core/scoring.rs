@@ -0,0 +1,516 @@
+use serde::{Deserialize, Serialize};
+
+use super::types::Frame;
+use super::variant::Variant;
+
+// Pins knocked down by a single ball
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Roll(u8);
+
+impl Roll {
+    // Constructor
+    pub fn build(pins: u8) -> Self {
+        Self(pins)
+    }
+
+    // Getter
+    pub fn pins(&self) -> u8 {
+        self.0
+    }
+}
+
+impl std::convert::From<u8> for Roll {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RollErrorKind {
+    TooManyPins,
+    GameComplete,
+    // No pins standing add up to the count, such as 1 in five-pin
+    NoSuchCount,
+    // A ball of a recorded frame lands in the frame after it
+    FrameOverrun,
+    // A recorded frame ends before its last ball is thrown
+    FrameIncomplete,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RollError {
+    frame: u8,
+    ball: u8,
+    kind: RollErrorKind,
+}
+
+impl RollError {
+    // Getter
+    pub fn frame(&self) -> u8 {
+        self.frame
+    }
+
+    pub fn ball(&self) -> u8 {
+        self.ball
+    }
+
+    pub fn kind(&self) -> RollErrorKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for RollError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            RollErrorKind::TooManyPins => write!(
+                f,
+                "More pins than are standing on ball {} of frame {}",
+                self.ball, self.frame
+            ),
+            RollErrorKind::GameComplete => write!(f, "Game is already complete"),
+            RollErrorKind::NoSuchCount => write!(
+                f,
+                "No pins standing add up to the count of ball {} of frame {}",
+                self.ball, self.frame
+            ),
+            RollErrorKind::FrameOverrun => write!(
+                f,
+                "Ball {} of frame {} is thrown after the frame is over",
+                self.ball, self.frame
+            ),
+            RollErrorKind::FrameIncomplete => write!(
+                f,
+                "Frame {} is not over after ball {}",
+                self.frame, self.ball
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RollError {}
+
+pub trait ScoringRules: Copy {
+    // Balls after a frame that count towards its score, the 10th frame never takes any
+    fn bonus_balls(&self, variant: Variant, frame: &[Roll]) -> usize;
+
+    // Score of a frame from its own balls and the bonus balls thrown so far
+    fn frame_score(&self, variant: Variant, frame: &[Roll], bonus: &[Roll]) -> u16;
+
+    // Whether a strike or spare in the 10th frame earns extra balls
+    fn fill_balls(&self) -> bool;
+}
+
+// Strikes count the next two balls and spares the next ball
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Traditional;
+
+impl ScoringRules for Traditional {
+    fn bonus_balls(&self, variant: Variant, frame: &[Roll]) -> usize {
+        variant.bonus_balls(frame)
+    }
+
+    fn frame_score(&self, _variant: Variant, frame: &[Roll], bonus: &[Roll]) -> u16 {
+        frame.iter().chain(bonus).map(|r| r.pins() as u16).sum()
+    }
+
+    fn fill_balls(&self) -> bool {
+        true
+    }
+}
+
+// World Bowling scoring, a strike is three racks and a spare is a rack plus the first ball
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CurrentFrame;
+
+impl ScoringRules for CurrentFrame {
+    fn bonus_balls(&self, _variant: Variant, _frame: &[Roll]) -> usize {
+        0
+    }
+
+    fn frame_score(&self, variant: Variant, frame: &[Roll], _bonus: &[Roll]) -> u16 {
+        let rack = variant.rack_value();
+
+        match frame {
+            [first, ..] if first.pins() == rack => rack as u16 * 3,
+            [first, second, ..] if first.pins() + second.pins() == rack => {
+                (rack + first.pins()) as u16
+            }
+            _ => frame.iter().map(|r| r.pins() as u16).sum(),
+        }
+    }
+
+    fn fill_balls(&self) -> bool {
+        false
+    }
+}
+
+// Rule set stored with each day of games
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleSet {
+    #[default]
+    Traditional,
+    CurrentFrame,
+}
+
+impl ScoringRules for RuleSet {
+    fn bonus_balls(&self, variant: Variant, frame: &[Roll]) -> usize {
+        match self {
+            Self::Traditional => Traditional.bonus_balls(variant, frame),
+            Self::CurrentFrame => CurrentFrame.bonus_balls(variant, frame),
+        }
+    }
+
+    fn frame_score(&self, variant: Variant, frame: &[Roll], bonus: &[Roll]) -> u16 {
+        match self {
+            Self::Traditional => Traditional.frame_score(variant, frame, bonus),
+            Self::CurrentFrame => CurrentFrame.frame_score(variant, frame, bonus),
+        }
+    }
+
+    fn fill_balls(&self) -> bool {
+        match self {
+            Self::Traditional => Traditional.fill_balls(),
+            Self::CurrentFrame => CurrentFrame.fill_balls(),
+        }
+    }
+}
+
+impl std::fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Traditional => write!(f, "Traditional"),
+            Self::CurrentFrame => write!(f, "Current Frame"),
+        }
+    }
+}
+
+// Scores a game ball by ball, rejecting rolls that can not happen
+#[derive(Debug, Clone)]
+pub struct Scorer<R: ScoringRules = Traditional> {
+    rules: R,
+    variant: Variant,
+    rolls: Vec<Roll>,
+    starts: Vec<usize>,
+    // First roll thrown at the rack standing now
+    rack_start: usize,
+    frame: u8,
+    ball: u8,
+    standing: u8,
+    complete: bool,
+}
+
+impl Scorer {
+    // Constructor
+    pub fn build() -> Self {
+        Self::build_with_rules(Traditional)
+    }
+
+    pub fn build_with(rolls: &[Roll]) -> Result<Self, RollError> {
+        Self::build_with_rolls(Traditional, rolls)
+    }
+
+    pub fn build_from_frames(frames: &[Frame]) -> Result<Self, RollError> {
+        Self::build_from_frames_with(Traditional, frames)
+    }
+}
+
+impl<R: ScoringRules> Scorer<R> {
+    // Constructor
+    pub fn build_with_rules(rules: R) -> Self {
+        Self::build_with_variant(Variant::TenPin, rules)
+    }
+
+    pub fn build_with_variant(variant: Variant, rules: R) -> Self {
+        Self {
+            rules,
+            variant,
+            rolls: Vec::new(),
+            starts: Vec::new(),
+            rack_start: 0,
+            frame: 1,
+            ball: 1,
+            standing: variant.rack_value(),
+            complete: false,
+        }
+    }
+
+    pub fn build_with_rolls(rules: R, rolls: &[Roll]) -> Result<Self, RollError> {
+        let mut scorer = Self::build_with_rules(rules);
+
+        for roll in rolls {
+            scorer.add_roll(*roll)?;
+        }
+
+        Ok(scorer)
+    }
+
+    pub fn build_from_frames_with(rules: R, frames: &[Frame]) -> Result<Self, RollError> {
+        let mut scorer = Self::build_with_rules(rules);
+        scorer.add_frames(frames)?;
+
+        Ok(scorer)
+    }
+
+    // Getter
+    pub fn rules(&self) -> R {
+        self.rules
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn rolls(&self) -> &[Roll] {
+        &self.rolls
+    }
+
+    pub fn standing(&self) -> u8 {
+        self.standing
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    // Frame and ball number of the next roll, `None` once the game is complete
+    pub fn next_ball(&self) -> Option<(u8, u8)> {
+        if self.complete {
+            return None;
+        }
+
+        Some((self.frame, self.ball))
+    }
+
+    // Method
+    pub fn add_roll(&mut self, roll: Roll) -> Result<(), RollError> {
+        let error = |kind| RollError {
+            frame: self.frame,
+            ball: self.ball,
+            kind,
+        };
+
+        if self.complete {
+            return Err(error(RollErrorKind::GameComplete));
+        }
+
+        if roll.pins() > self.standing {
+            return Err(error(RollErrorKind::TooManyPins));
+        }
+
+        let mut counts = self.rolls[self.rack_start..]
+            .iter()
+            .map(Roll::pins)
+            .collect::<Vec<u8>>();
+        counts.push(roll.pins());
+
+        if !self.variant.fits_rack(&counts) {
+            return Err(error(RollErrorKind::NoSuchCount));
+        }
+
+        if self.ball == 1 {
+            self.starts.push(self.rolls.len());
+        }
+
+        self.rolls.push(roll);
+        self.standing -= roll.pins();
+
+        let rack = self.variant.rack_value();
+        let balls = self.variant.balls();
+
+        if self.frame < 10 {
+            if self.standing == 0 || self.ball == balls {
+                self.frame += 1;
+                self.ball = 1;
+                self.standing = rack;
+                self.rack_start = self.rolls.len();
+            } else {
+                self.ball += 1;
+            }
+
+            return Ok(());
+        }
+
+        // Only the 10th frame resets the rack and, when the rules allow, earns extra balls
+        let start = self.starts[9];
+        let first_two: u8 = self.rolls[start..].iter().take(2).map(Roll::pins).sum();
+
+        if self.rules.fill_balls() {
+            match self.ball {
+                3 => self.complete = true,
+                2 if balls == 2 && first_two < rack => self.complete = true,
+                _ => self.ball += 1,
+            }
+        } else if self.standing == 0 || self.ball == balls {
+            self.complete = true;
+        } else {
+            self.ball += 1;
+        }
+
+        if self.standing == 0 {
+            self.standing = rack;
+            self.rack_start = self.rolls.len();
+        }
+
+        Ok(())
+    }
+
+    // Frames are added in order until the first one that is not played. A frame that can not be
+    // bowled is an error, the frames before it are kept
+    pub fn add_frames(&mut self, frames: &[Frame]) -> Result<(), RollError> {
+        for frame in frames {
+            let balls = frame.balls_with(self.variant);
+
+            if balls.is_empty() {
+                break;
+            }
+
+            let n = self.frame;
+            let mut next = self.clone();
+
+            for (ball, count) in (1..).zip(balls.iter()) {
+                next.add_roll(Roll::build(*count))?;
+
+                // Every ball but the last has to stay in the frame, and the last has to end it
+                let over = if n == 10 {
+                    next.complete
+                } else {
+                    next.frame != n
+                };
+                let last = ball as usize == balls.len();
+
+                if over && !last {
+                    let kind = if n == 10 {
+                        RollErrorKind::GameComplete
+                    } else {
+                        RollErrorKind::FrameOverrun
+                    };
+
+                    // Rules without fill balls still score a 10th frame recorded with them
+                    if n == 10 && !self.rules.fill_balls() {
+                        *self = next;
+                    }
+
+                    return Err(RollError {
+                        frame: n,
+                        ball: ball + 1,
+                        kind,
+                    });
+                }
+
+                if last && !over {
+                    return Err(RollError {
+                        frame: n,
+                        ball,
+                        kind: RollErrorKind::FrameIncomplete,
+                    });
+                }
+            }
+
+            *self = next;
+        }
+
+        Ok(())
+    }
+
+    fn frame_rolls(&self, idx: usize) -> &[Roll] {
+        let end = self
+            .starts
+            .get(idx + 1)
+            .copied()
+            .unwrap_or(self.rolls.len());
+
+        &self.rolls[self.starts[idx]..end]
+    }
+
+    fn is_frame_done(&self, idx: usize) -> bool {
+        if idx == 9 {
+            return self.complete;
+        }
+
+        idx < self.starts.len() && self.frame as usize > idx + 1
+    }
+
+    // Balls after frame `idx` counted as its bonus
+    fn bonus_rolls(&self, idx: usize) -> usize {
+        if idx == 9 {
+            return 0;
+        }
+
+        self.rules.bonus_balls(self.variant, self.frame_rolls(idx))
+    }
+
+    // Score of frame `idx` with whatever bonus balls have been thrown so far
+    fn frame_points(&self, idx: usize) -> u16 {
+        let frame = self.frame_rolls(idx);
+        let start = self.starts[idx] + frame.len();
+        let end = (start + self.bonus_rolls(idx)).min(self.rolls.len());
+
+        self.rules
+            .frame_score(self.variant, frame, &self.rolls[start..end])
+    }
+
+    // Pins and bonuses of frame `idx`, `None` while the frame or its bonus is incomplete
+    fn frame_score(&self, idx: usize) -> Option<u16> {
+        if idx >= self.starts.len() || !self.is_frame_done(idx) {
+            return None;
+        }
+
+        let end = self.starts[idx] + self.frame_rolls(idx).len() + self.bonus_rolls(idx);
+
+        if end > self.rolls.len() {
+            return None;
+        }
+
+        Some(self.frame_points(idx))
+    }
+
+    // Running total after each frame, `None` from the first frame that is not final
+    pub fn frame_scores(&self) -> Vec<Option<u16>> {
+        let mut total: Option<u16> = Some(0);
+
+        (0..10)
+            .map(|idx| {
+                total = total.zip(self.frame_score(idx)).map(|(t, s)| t + s);
+                total
+            })
+            .collect()
+    }
+
+    pub fn score_n(&self, frame_no: u8) -> Option<u16> {
+        match frame_no {
+            0 => Some(0),
+            1..=10 => self.frame_scores()[(frame_no - 1) as usize],
+            _ => None,
+        }
+    }
+
+    // Points of every frame started so far, counting the bonus balls already thrown
+    pub fn score(&self) -> u16 {
+        (0..self.starts.len())
+            .map(|idx| self.frame_points(idx))
+            .sum()
+    }
+
+    // Completed frames in the stored layout, unplayed or unfinished frames are uninitialized
+    pub fn frames(&self) -> Vec<Frame> {
+        (0..10)
+            .map(|idx| {
+                if idx >= self.starts.len() || !self.is_frame_done(idx) {
+                    return Frame::Uninit;
+                }
+
+                match self.frame_rolls(idx) {
+                    [strike] => Frame::TwoFrame(strike.pins(), 0),
+                    rolls => Frame::from(rolls.iter().map(Roll::pins).collect::<Vec<u8>>()),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for Scorer {
+    fn default() -> Self {
+        Self::build()
+    }
+}
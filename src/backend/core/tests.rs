@@ -2,5 +2,15 @@ mod date;
 mod frame;
 mod game;
 mod games;
+mod pins;
+mod scoring;
+mod variant;
 
-use super::types::{Date, Frame, Game, Games};
+use super::scoring::{
+    CurrentFrame, Roll, RollErrorKind, RuleSet, Scorer, ScoringRules, Traditional,
+};
+use super::types::{
+    Bowler, Date, Deadwood, Frame, Game, Games, Marker, ParseScoreErrorKind, Pins, Session,
+    SessionKey, SessionLabel, Split, Time,
+};
+use super::variant::Variant;
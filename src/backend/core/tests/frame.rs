@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
 
-use super::Frame;
+use mongodb::bson::{doc, from_bson, to_bson, Bson};
+
+use super::{Frame, ParseScoreErrorKind, Pins, Split, Variant};
 
 #[test]
 fn build() {
@@ -93,6 +95,124 @@ fn is_valid_three_edge() {
 
 // TODO: Make tests for is_valid_no
 
+#[test]
+fn is_valid_leave() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+    let ten = Pins::build_with(&[10]).unwrap();
+
+    assert!(Frame::TwoLeave(Pins::NONE, Pins::NONE).is_valid());
+    assert!(Frame::TwoLeave(split, Pins::NONE).is_valid());
+    assert!(Frame::TwoLeave(split, seven).is_valid());
+    assert!(Frame::TwoLeave(split, split).is_valid());
+    assert!(Frame::TwoLeave(Pins::ALL, Pins::ALL).is_valid());
+
+    // Pins can not stand back up
+    assert!(!Frame::TwoLeave(seven, split).is_valid());
+    assert!(!Frame::TwoLeave(seven, ten).is_valid());
+}
+
+#[test]
+fn is_valid_leave_three() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+
+    // Strike, rack reset
+    assert!(Frame::ThreeLeave(Pins::NONE, split, seven).is_valid());
+    assert!(Frame::ThreeLeave(Pins::NONE, Pins::NONE, split).is_valid());
+    assert!(Frame::ThreeLeave(Pins::NONE, Pins::NONE, Pins::NONE).is_valid());
+    assert!(!Frame::ThreeLeave(Pins::NONE, seven, split).is_valid());
+
+    // Spare, rack reset
+    assert!(Frame::ThreeLeave(split, Pins::NONE, seven).is_valid());
+    assert!(Frame::ThreeLeave(split, Pins::NONE, Pins::ALL).is_valid());
+
+    // Open, no third ball
+    assert!(!Frame::ThreeLeave(split, seven, Pins::NONE).is_valid());
+
+    assert!(Frame::ThreeLeave(split, Pins::NONE, seven).is_valid_no(10));
+    assert!(!Frame::ThreeLeave(split, Pins::NONE, seven).is_valid_no(9));
+    assert!(!Frame::TwoLeave(split, Pins::NONE).is_valid_no(10));
+    assert!(Frame::TwoLeave(split, seven).is_valid_no(10));
+}
+
+#[test]
+fn counts_leave() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+
+    assert_eq!(
+        Frame::TwoLeave(Pins::NONE, Pins::NONE).counts(),
+        Frame::TwoFrame(10, 0)
+    );
+    assert_eq!(
+        Frame::TwoLeave(split, seven).counts(),
+        Frame::TwoFrame(8, 1)
+    );
+    assert_eq!(
+        Frame::TwoLeave(split, Pins::NONE).counts(),
+        Frame::TwoFrame(8, 2)
+    );
+    assert_eq!(
+        Frame::TwoLeave(Pins::ALL, Pins::ALL).counts(),
+        Frame::TwoFrame(0, 0)
+    );
+
+    assert_eq!(
+        Frame::ThreeLeave(Pins::NONE, Pins::NONE, Pins::NONE).counts(),
+        Frame::ThreeFrame(10, 10, 10)
+    );
+    assert_eq!(
+        Frame::ThreeLeave(Pins::NONE, split, Pins::NONE).counts(),
+        Frame::ThreeFrame(10, 8, 2)
+    );
+    assert_eq!(
+        Frame::ThreeLeave(split, Pins::NONE, seven).counts(),
+        Frame::ThreeFrame(8, 2, 9)
+    );
+
+    assert_eq!(Frame::TwoFrame(9, 0).counts(), Frame::TwoFrame(9, 0));
+    assert_eq!(Frame::Uninit.counts(), Frame::Uninit);
+}
+
+#[test]
+fn leaves() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+
+    assert_eq!(
+        Frame::TwoLeave(split, Pins::NONE).leaves(),
+        Some(vec![split, Pins::NONE])
+    );
+    assert_eq!(
+        Frame::ThreeLeave(Pins::NONE, split, Pins::NONE).leaves(),
+        Some(vec![Pins::NONE, split, Pins::NONE])
+    );
+    assert_eq!(Frame::TwoFrame(8, 2).leaves(), None);
+    assert_eq!(Frame::Uninit.leaves(), None);
+}
+
+#[test]
+fn score_leave() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+
+    for bits in 0..=Pins::ALL.bits() {
+        let leave = Pins::from_bits(bits).unwrap();
+
+        let test = Frame::TwoLeave(leave, leave);
+
+        assert_eq!(test.score(), 10 - leave.standing());
+        assert_eq!(test.is_strike(), leave == Pins::NONE);
+        assert!(!test.is_spare());
+
+        let test = Frame::TwoLeave(leave, Pins::NONE);
+
+        assert_eq!(test.score(), 10);
+        assert_eq!(test.is_spare(), leave != Pins::NONE);
+    }
+
+    assert_eq!(Frame::ThreeLeave(split, Pins::NONE, split).score(), 18);
+}
+
 #[test]
 fn score_unit() {
     let uninit = Frame::Uninit;
@@ -484,6 +604,176 @@ fn from_three_tuple() {
     }
 }
 
+#[test]
+fn first_leave() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+
+    assert_eq!(
+        Frame::TwoLeave(split, Pins::NONE).first_leave(),
+        Some(split)
+    );
+    assert_eq!(
+        Frame::ThreeLeave(Pins::NONE, split, Pins::NONE).first_leave(),
+        Some(Pins::NONE)
+    );
+    assert_eq!(Frame::TwoFrame(8, 2).first_leave(), None);
+}
+
+#[test]
+fn split() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+    let spare = Pins::build_with(&[2, 4, 5]).unwrap();
+
+    let converted = Frame::TwoLeave(split, Pins::NONE);
+    assert!(converted.is_split());
+    assert_eq!(converted.split(), Some(Split::SevenTen));
+    assert!(converted.is_split_converted());
+
+    let missed = Frame::TwoLeave(split, seven);
+    assert!(missed.is_split());
+    assert!(!missed.is_split_converted());
+
+    let tenth = Frame::ThreeLeave(split, Pins::NONE, Pins::NONE);
+    assert!(tenth.is_split());
+    assert!(tenth.is_split_converted());
+    assert_eq!(tenth.split_chances(), 1);
+
+    // The rack after a strike in the 10th can split too
+    let after_strike = Frame::ThreeLeave(Pins::NONE, split, Pins::NONE);
+    assert!(after_strike.is_split());
+    assert_eq!(after_strike.split(), Some(Split::SevenTen));
+    assert!(after_strike.is_split_converted());
+    assert_eq!(after_strike.split_chances(), 1);
+
+    // A split left by the fill ball has no ball to convert it
+    let fill_ball = Frame::ThreeLeave(spare, Pins::NONE, split);
+    assert!(!fill_ball.is_split());
+    assert_eq!(fill_ball.split_chances(), 1);
+
+    // Strikes are no chance at a split
+    assert_eq!(Frame::TwoLeave(Pins::NONE, Pins::NONE).split_chances(), 0);
+    assert_eq!(
+        Frame::ThreeLeave(Pins::NONE, Pins::NONE, split).split_chances(),
+        0
+    );
+
+    let not_split = Frame::TwoLeave(spare, Pins::NONE);
+    assert!(!not_split.is_split());
+    assert_eq!(not_split.split(), None);
+    assert!(!not_split.is_split_converted());
+
+    assert!(!Frame::TwoFrame(8, 2).is_split());
+    assert!(!Frame::Uninit.is_split());
+}
+
+#[test]
+fn from_leave_tuple() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+
+    let test = Frame::from((split, Pins::NONE));
+    assert!(matches!(test, Frame::TwoLeave(l1, l2) if l1 == split && l2 == Pins::NONE));
+
+    let test = Frame::from((Pins::NONE, split, Pins::NONE));
+    assert!(
+        matches!(test, Frame::ThreeLeave(l1, l2, l3) if l1 == Pins::NONE && l2 == split && l3 == Pins::NONE)
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(Frame::TwoFrame(10, 0).to_string(), "X");
+    assert_eq!(Frame::TwoFrame(9, 1).to_string(), "9/");
+    assert_eq!(Frame::TwoFrame(0, 10).to_string(), "-/");
+    assert_eq!(Frame::TwoFrame(8, 0).to_string(), "8-");
+    assert_eq!(Frame::TwoFrame(0, 0).to_string(), "--");
+    assert_eq!(Frame::TwoFrame(7, 2).to_string(), "72");
+    assert_eq!(Frame::ThreeFrame(10, 10, 10).to_string(), "XXX");
+    assert_eq!(Frame::ThreeFrame(10, 10, 7).to_string(), "XX7");
+    assert_eq!(Frame::ThreeFrame(10, 7, 3).to_string(), "X7/");
+    assert_eq!(Frame::ThreeFrame(9, 1, 10).to_string(), "9/X");
+    assert_eq!(Frame::ThreeFrame(9, 1, 0).to_string(), "9/-");
+    assert_eq!(Frame::Uninit.to_string(), "");
+
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    assert_eq!(Frame::TwoLeave(split, Pins::NONE).to_string(), "8/");
+}
+
+#[test]
+fn from_str() {
+    for i in 0..=10 {
+        for j in 0..=10 - i {
+            let test = Frame::TwoFrame(i, j);
+
+            assert_eq!(test.to_string().parse::<Frame>(), Ok(test));
+        }
+    }
+
+    for i in 0..=10 {
+        let test = Frame::ThreeFrame(10, 10, i);
+
+        assert_eq!(test.to_string().parse::<Frame>(), Ok(test));
+
+        let test = Frame::ThreeFrame(i, 10 - i, 10);
+
+        assert_eq!(test.to_string().parse::<Frame>(), Ok(test));
+    }
+
+    assert_eq!("X X 7".parse::<Frame>(), Ok(Frame::ThreeFrame(10, 10, 7)));
+    assert_eq!("F/".parse::<Frame>(), Ok(Frame::TwoFrame(0, 10)));
+    assert_eq!("x".parse::<Frame>(), Ok(Frame::TwoFrame(10, 0)));
+}
+
+#[test]
+fn from_str_error() {
+    let test = "/".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::SpareFirst);
+    assert_eq!(test.position(), 0);
+
+    let test = "7X".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::StrikeNotFirst);
+    assert_eq!(test.position(), 1);
+
+    let test = "73".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::ExpectedSpare);
+
+    let test = "7 4".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::TooManyPins);
+    assert_eq!(test.position(), 2);
+
+    let test = "7".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::IncompleteFrame);
+    assert_eq!(test.position(), 1);
+
+    let test = "9/X1".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::ExtraBall);
+    assert_eq!(test.position(), 3);
+    assert_eq!(test.frame(), 10);
+
+    let test = "7?".parse::<Frame>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::InvalidSymbol('?'));
+}
+
+#[test]
+fn serde_count_frame() {
+    // Count-only frames stored before leaves were tracked
+    let stored = doc! { "TwoFrame": [9, 1] };
+
+    let test: Frame = from_bson(Bson::Document(stored)).unwrap();
+
+    assert_eq!(test, Frame::TwoFrame(9, 1));
+}
+
+#[test]
+fn serde_leave_frame() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let test = Frame::TwoLeave(split, Pins::NONE);
+
+    let stored = to_bson(&test).unwrap();
+
+    assert_eq!(from_bson::<Frame>(stored).unwrap(), test);
+}
+
 #[test]
 fn partialeq() {
     let test_tuple = (5, 5);
@@ -495,6 +785,14 @@ fn partialeq() {
 
     assert_eq!(test, same);
     assert_ne!(test, diff);
+
+    let split = Pins::build_with(&[7, 10]).unwrap();
+
+    assert_eq!(
+        Frame::TwoLeave(split, Pins::NONE),
+        Frame::TwoLeave(split, Pins::NONE)
+    );
+    assert_ne!(Frame::TwoLeave(split, Pins::NONE), Frame::TwoFrame(8, 2));
 }
 
 #[test]
@@ -575,3 +873,37 @@ fn partialord() {
         }
     }
 }
+
+#[test]
+fn with_no_tap() {
+    let tenpin = Variant::TenPin;
+
+    assert_eq!(
+        Frame::TwoFrame(9, 0).with_no_tap(tenpin, 9),
+        Frame::TwoFrame(10, 0)
+    );
+    assert_eq!(
+        Frame::TwoFrame(8, 1).with_no_tap(tenpin, 9),
+        Frame::TwoFrame(8, 1)
+    );
+    assert_eq!(
+        Frame::TwoFrame(8, 0).with_no_tap(tenpin, 8),
+        Frame::TwoFrame(10, 0)
+    );
+    // Only the first ball of a rack can be a no-tap strike
+    assert_eq!(
+        Frame::TwoFrame(0, 9).with_no_tap(tenpin, 9),
+        Frame::TwoFrame(0, 9)
+    );
+    assert_eq!(
+        Frame::ThreeFrame(9, 9, 9).with_no_tap(tenpin, 9),
+        Frame::ThreeFrame(10, 10, 10)
+    );
+    assert_eq!(
+        Frame::ThreeFrame(7, 3, 9).with_no_tap(tenpin, 9),
+        Frame::ThreeFrame(7, 3, 10)
+    );
+
+    assert!(Frame::TwoFrame(9, 0).with_no_tap(tenpin, 9).is_strike());
+    assert!(!Frame::TwoFrame(9, 1).with_no_tap(tenpin, 9).is_valid());
+}
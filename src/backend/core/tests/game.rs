@@ -1,6 +1,8 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-use super::{Frame, Game};
+use mongodb::bson::{doc, from_bson, to_bson, Bson};
+
+use super::{Frame, Game, Marker, ParseScoreErrorKind, Pins};
 
 fn sample_games() -> HashMap<u16, Game> {
     let mut games: HashMap<u16, Game> = HashMap::new();
@@ -302,7 +304,132 @@ fn score() {
     }
 }
 
-// TODO: Make test for score_n
+#[test]
+fn score_leave() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+
+    let leaves = Game::build_with(
+        1,
+        (1..=10)
+            .map(|n| match n {
+                10 => Frame::ThreeLeave(Pins::NONE, Pins::NONE, split),
+                n if n % 2 == 1 => Frame::TwoLeave(split, Pins::NONE),
+                _ => Frame::TwoLeave(split, seven),
+            })
+            .collect(),
+    );
+    let counts = Game::build_with(
+        1,
+        (1..=10)
+            .map(|n| match n {
+                10 => Frame::ThreeFrame(10, 10, 8),
+                n if n % 2 == 1 => Frame::TwoFrame(8, 2),
+                _ => Frame::TwoFrame(8, 1),
+            })
+            .collect(),
+    );
+
+    assert!(leaves.is_valid());
+    assert_eq!(leaves.score(), counts.score());
+    assert_eq!(leaves.pin_count(), counts.pin_count());
+    assert_eq!(leaves.num_strikes(), counts.num_strikes());
+    assert_eq!(leaves.num_spares(), counts.num_spares());
+    assert_eq!(leaves.to_string(), counts.to_string());
+
+    for n in 1..=10 {
+        assert_eq!(leaves.score_n(n), counts.score_n(n));
+    }
+}
+
+#[test]
+fn num_splits() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+    let spare = Pins::build_with(&[2, 4, 5]).unwrap();
+
+    let test = Game::build_with(
+        1,
+        (1..=10)
+            .map(|n| match n {
+                1..=3 => Frame::TwoLeave(split, Pins::NONE),
+                4 | 5 => Frame::TwoLeave(split, seven),
+                6 | 7 => Frame::TwoLeave(spare, Pins::NONE),
+                8 => Frame::TwoLeave(Pins::NONE, Pins::NONE),
+                9 => Frame::TwoFrame(7, 2),
+                _ => Frame::ThreeLeave(Pins::NONE, split, Pins::NONE),
+            })
+            .collect(),
+    );
+
+    // The strike is no chance at a split, the rack after the strike in the 10th is
+    assert_eq!(test.num_splits(), 6);
+    assert_eq!(test.num_converted_splits(), 4);
+    assert_eq!(test.split_chances(), 8);
+}
+
+#[test]
+fn score_n() {
+    let games = sample_games();
+
+    for key in games.keys() {
+        assert_eq!(games[key].score_n(10), Some(*key));
+    }
+
+    let test = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+    let running = [20, 38, 46, 76, 103, 123, 142, 151, 181, 211];
+
+    for (n, score) in (1..=10).zip(running) {
+        assert_eq!(test.score_n(n), Some(score));
+    }
+
+    let test = "X 9/ 8- X X".parse::<Game>().unwrap();
+
+    assert_eq!(test.score_n(3), Some(46));
+    assert_eq!(test.score_n(4), None);
+    assert_eq!(test.score_n(5), None);
+}
+
+#[test]
+fn possible_score() {
+    let games = sample_games();
+
+    for key in games.keys() {
+        assert_eq!(games[key].possible_score(), *key);
+        assert_eq!(games[key].guaranteed_score(), *key);
+    }
+
+    let test = Game::build(1);
+
+    assert_eq!(test.possible_score(), 300);
+    assert_eq!(test.guaranteed_score(), 0);
+}
+
+#[test]
+fn possible_score_partial() {
+    let mut test = Game::build(1);
+
+    test.frames_mut()[0] = Frame::TwoFrame(9, 1);
+    test.frames_mut()[1] = Frame::TwoFrame(10, 0);
+
+    // 20 + 30 + 8 * 30
+    assert_eq!(test.possible_score(), 290);
+    // 20 + 10
+    assert_eq!(test.guaranteed_score(), 30);
+
+    for n in 3..=9 {
+        test.frames_mut()[n - 1] = Frame::TwoFrame(10, 0);
+    }
+
+    assert_eq!(test.possible_score(), 290);
+    assert_eq!(test.guaranteed_score(), 20 + 30 * 6 + 20 + 10);
+
+    test.frames_mut()[8] = Frame::TwoFrame(7, 2);
+
+    // 20 + 30 * 5 + 27 + 19 + 9 + 30
+    assert_eq!(test.possible_score(), 255);
+    assert_eq!(test.guaranteed_score(), 225);
+}
 
 #[test]
 fn pin_count_two() {
@@ -900,6 +1027,68 @@ fn avg_first_ball_pinfall_three() {
     }
 }
 
+#[test]
+fn display() {
+    let games = sample_games();
+
+    assert_eq!(games[&300].to_string(), "X X X X X X X X X XXX");
+    assert_eq!(games[&200].to_string(), "X 9/ X 9/ X 9/ X 9/ X 9/X");
+    assert_eq!(games[&0].to_string(), "-- -- -- -- -- -- -- -- -- --");
+    assert_eq!(Game::build(1).to_string(), "");
+}
+
+#[test]
+fn from_str() {
+    let games = sample_games();
+
+    for game in games.values() {
+        let test = game.to_string().parse::<Game>().unwrap();
+
+        assert!(test
+            .frames()
+            .iter()
+            .zip(game.frames().iter())
+            .all(|(f1, f2)| f1 == f2));
+    }
+
+    let test = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+
+    assert!(test.is_valid());
+    assert_eq!(test.frames()[2], Frame::TwoFrame(8, 0));
+    assert_eq!(test.frames()[9], Frame::ThreeFrame(10, 10, 10));
+    assert_eq!(test.score(), 211);
+
+    let test = "X 9/ 8-".parse::<Game>().unwrap();
+
+    assert_eq!(test.frames()[2], Frame::TwoFrame(8, 0));
+    assert_eq!(test.frames()[3], Frame::Uninit);
+}
+
+#[test]
+fn from_str_error() {
+    let test = "X 9/ 8- X X X 7/ 9- X X X 7 1".parse::<Game>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::ExtraBall);
+    assert_eq!(test.position(), 28);
+
+    let test = "X 9/ /".parse::<Game>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::SpareFirst);
+    assert_eq!(test.frame(), 3);
+    assert_eq!(test.position(), 5);
+
+    let test = "X 9/ 8- X X X 7/ 9- X 9/".parse::<Game>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::IncompleteFrame);
+    assert_eq!(test.frame(), 10);
+    assert_eq!(test.position(), 24);
+
+    let test = "X 9/ 8- X X X 7/ 9- X 9-X".parse::<Game>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::ExtraBall);
+    assert_eq!(test.position(), 24);
+
+    let test = "X 8X".parse::<Game>().unwrap_err();
+    assert_eq!(test.kind(), ParseScoreErrorKind::StrikeNotFirst);
+    assert_eq!(test.frame(), 2);
+}
+
 #[test]
 fn partialeq() {
     let games = sample_games();
@@ -940,3 +1129,152 @@ fn partialord() {
         }
     }
 }
+
+#[test]
+fn set_marker() {
+    let mut test = "F/ X 8- G7".parse::<Game>().unwrap();
+
+    assert_eq!(test.marker(1, 1), Some(Marker::Foul));
+    assert_eq!(test.marker(4, 1), Some(Marker::Gutter));
+    assert_eq!(test.marker(3, 2), None);
+
+    test.set_marker(3, 2, Some(Marker::Gutter));
+    test.set_marker(3, 1, Some(Marker::Pocket));
+    assert_eq!(test.marker(3, 2), Some(Marker::Gutter));
+
+    test.set_marker(3, 2, Some(Marker::Foul));
+    assert_eq!(test.marker(3, 2), Some(Marker::Foul));
+    assert_eq!(test.markers().len(), 4);
+
+    test.set_marker(3, 2, None);
+    assert_eq!(test.marker(3, 2), None);
+    assert_eq!(test.markers().len(), 3);
+}
+
+#[test]
+fn is_valid_marker() {
+    let mut test = "X 9/ 8- X X X 7/ 9- X 9/X".parse::<Game>().unwrap();
+
+    assert!(test.is_valid());
+
+    test.set_marker(3, 2, Some(Marker::Foul));
+    test.set_marker(2, 1, Some(Marker::Split));
+    test.set_marker(1, 1, Some(Marker::Pocket));
+    test.set_marker(10, 3, Some(Marker::Pocket));
+    assert!(test.is_valid());
+
+    // Foul on a ball with pinfall
+    test.set_marker(3, 1, Some(Marker::Foul));
+    assert!(!test.is_valid());
+    test.set_marker(3, 1, None);
+
+    // Split on a second ball
+    test.set_marker(2, 2, Some(Marker::Split));
+    assert!(!test.is_valid());
+    test.set_marker(2, 2, None);
+
+    // No second ball after a strike
+    test.set_marker(1, 2, Some(Marker::Gutter));
+    assert!(!test.is_valid());
+}
+
+#[test]
+fn display_marker() {
+    let mut test = "F/ X 8- G7 X X X X X XXX".parse::<Game>().unwrap();
+
+    assert_eq!(test.to_string(), "F/ X 8- G7 X X X X X XXX");
+
+    test.set_marker(3, 2, Some(Marker::Gutter));
+    test.set_marker(2, 1, Some(Marker::Pocket));
+    test.set_marker(4, 1, None);
+    assert_eq!(test.to_string(), "F/ X 8G -7 X X X X X XXX");
+
+    let scorecard = test.scorecard().to_string();
+
+    assert!(scorecard.contains("F | /"));
+    assert!(scorecard.contains("8 | G"));
+    assert!(scorecard.ends_with("Pocket: 2.1"));
+}
+
+#[test]
+fn serde_marker() {
+    let mut test = "F/ X 8-".parse::<Game>().unwrap();
+    test.set_marker(2, 1, Some(Marker::Pocket));
+
+    let stored = to_bson(&test).unwrap();
+
+    assert_eq!(from_bson::<Game>(stored).unwrap(), test);
+
+    // Games stored before markers were tracked
+    let stored = doc! {
+        "game_num": 1,
+        "frames": to_bson(&Game::build(1).frames()).unwrap(),
+    };
+
+    assert_eq!(
+        from_bson::<Game>(Bson::Document(stored)).unwrap(),
+        Game::build(1)
+    );
+}
+
+#[test]
+fn num_markers() {
+    let mut test = "F/ X 8- G7 F- X X X X XXX".parse::<Game>().unwrap();
+    test.set_marker(2, 1, Some(Marker::Pocket));
+
+    assert_eq!(test.num_fouls(), 2);
+    assert_eq!(test.num_gutters(), 1);
+    assert_eq!(test.num_pocket_hits(), 1);
+    assert_eq!(test.num_markers(Marker::Split), 0);
+}
+
+#[test]
+fn no_tap() {
+    let mut frames = vec![Frame::TwoFrame(9, 0); 9];
+    frames.push(Frame::ThreeFrame(9, 9, 9));
+
+    let mut test = Game::build_with(1, frames);
+
+    assert!(!test.is_valid());
+    assert!(!test.is_no_tap());
+
+    test.set_no_tap(Some(9));
+
+    assert!(test.is_valid());
+    assert_eq!(test.no_tap(), Some(9));
+    assert_eq!(test.score(), 300);
+    assert_eq!(test.num_strikes(), 12);
+    assert_eq!(test.pin_count(), 108);
+    assert_eq!(test.to_string(), "X X X X X X X X X XXX");
+
+    // Pins on the second ball never count as a no-tap strike
+    test.frames_mut()[0] = Frame::TwoFrame(0, 9);
+    test.set_no_tap(Some(8));
+
+    assert!(test.is_valid());
+    assert_eq!(test.score(), 279);
+
+    // A spare can not follow a no-tap strike
+    test.frames_mut()[0] = Frame::TwoFrame(9, 1);
+
+    assert!(!test.is_valid());
+
+    test.frames_mut()[0] = Frame::TwoFrame(9, 0);
+    test.set_no_tap(Some(11));
+
+    assert!(!test.is_valid());
+}
+
+#[test]
+fn serde_no_tap() {
+    let mut test = Game::build(1);
+    test.set_no_tap(Some(9));
+
+    let stored = to_bson(&test).unwrap();
+
+    assert_eq!(from_bson::<Game>(stored).unwrap(), test);
+
+    let stored = to_bson(&Game::build(1)).unwrap();
+
+    assert!(!stored.as_document().unwrap().contains_key("no_tap"));
+}
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use mongodb::bson::{from_bson, to_bson, Bson};
+
 use super::*;
 
 fn sample_games() -> VecDeque<Game> {
@@ -257,3 +259,223 @@ fn avg_first_ball_pinfall() {
 
     assert_eq!(avg, calc_avg);
 }
+
+#[test]
+fn split_rate() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let seven = Pins::build_with(&[7]).unwrap();
+
+    let mut test = Games::build();
+
+    test.add_game(Game::build_with(
+        1,
+        (1..=10)
+            .map(|n| match n {
+                1 => Frame::TwoLeave(split, Pins::NONE),
+                2 => Frame::TwoLeave(split, seven),
+                3..=5 => Frame::TwoLeave(Pins::NONE, Pins::NONE),
+                _ => Frame::TwoFrame(9, 0),
+            })
+            .collect(),
+    ));
+    test.add_game(Game::build_with(
+        2,
+        (1..=10)
+            .map(|n| match n {
+                1 => Frame::TwoLeave(split, seven),
+                2..=5 => Frame::TwoLeave(seven, Pins::NONE),
+                _ => Frame::TwoFrame(9, 0),
+            })
+            .collect(),
+    ));
+
+    // Strikes are left out of the chances
+    assert_eq!(test.split_rate(), 3.0 / 7.0);
+    assert_eq!(test.split_conversion_rate(), 1.0 / 3.0);
+}
+
+#[test]
+fn rates_over_many_games() {
+    let split = Pins::build_with(&[7, 10]).unwrap();
+    let mut test = Games::build();
+
+    // More strikes and splits than fit in a u8
+    for n in 1..=30 {
+        test.add_game("X X X X X X X X X XXX".parse::<Game>().unwrap());
+        test.add_game(Game::build_with(
+            n,
+            (1..=10)
+                .map(|n| match n {
+                    10 => Frame::TwoFrame(9, 0),
+                    _ => Frame::TwoLeave(split, Pins::NONE),
+                })
+                .collect(),
+        ));
+    }
+
+    assert_eq!(test.strike_rate(), 360.0 / 660.0);
+    assert_eq!(test.split_rate(), 1.0);
+    assert_eq!(test.split_conversion_rate(), 1.0);
+}
+
+#[test]
+fn foul_count() {
+    let mut test = Games::build();
+
+    test.add_game("F/ X 8- G7 F- X X X X XXX".parse::<Game>().unwrap());
+    test.add_game("X X G- F9".parse::<Game>().unwrap());
+
+    assert_eq!(test.foul_count(), 3);
+    assert_eq!(test.gutter_count(), 2);
+    assert_eq!(test.pocket_count(), 0);
+}
+
+#[test]
+fn rules() {
+    let mut game = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+    game.frames_mut()[9] = Frame::TwoFrame(10, 0);
+    let mut test = Games::build_with(Date::build_with(2024, 2, 5), game);
+
+    assert_eq!(test.rules(), RuleSet::Traditional);
+    assert!(!test.is_valid());
+
+    test.set_rules(RuleSet::CurrentFrame);
+
+    assert!(test.is_valid());
+    assert_eq!(test.average(), 233.0);
+
+    // Stored with the games, older documents default to traditional
+    let bson = to_bson(&test).unwrap();
+    assert_eq!(
+        from_bson::<Games>(bson).unwrap().rules(),
+        RuleSet::CurrentFrame
+    );
+
+    let mut doc = to_bson(&test).unwrap().as_document().unwrap().clone();
+    doc.remove("rules");
+    assert_eq!(
+        from_bson::<Games>(Bson::Document(doc)).unwrap().rules(),
+        RuleSet::Traditional
+    );
+}
+
+#[test]
+fn no_tap() {
+    let normal = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+
+    let mut frames = vec![Frame::TwoFrame(9, 0); 9];
+    frames.push(Frame::ThreeFrame(9, 9, 9));
+    let mut no_tap = Game::build_with(2, frames);
+    no_tap.set_no_tap(Some(9));
+
+    let mut test =
+        Games::build_from_vec(Date::build_with(2024, 2, 5), vec![normal.clone(), no_tap]);
+
+    assert!(test.is_valid());
+    assert!(!test.includes_no_tap());
+    assert_eq!(test.average(), normal.score() as f32);
+    assert_eq!(
+        test.strike_rate(),
+        normal.num_strikes() as f32 / normal.strike_chances() as f32
+    );
+
+    test.set_include_no_tap(true);
+
+    assert_eq!(test.average(), (normal.score() + 300) as f32 / 2.0);
+}
+
+#[test]
+fn bowler() {
+    let date = Date::build_with(2024, 2, 5);
+
+    let test = Games::build_with(date, Game::build(1));
+
+    assert_eq!(test.bowler(), &Bowler::default());
+    assert_eq!(test.bowler().name(), Bowler::DEFAULT_NAME);
+    assert_eq!(test.session_id(), 1);
+
+    let key = SessionKey::build(Bowler::build("Sam"), date, 2);
+    let test = Games::build_for(key.clone(), Vec::new());
+
+    assert_eq!(test.key(), &key);
+    assert_eq!(test.bowler().to_string(), "Sam");
+    assert_eq!(test.date(), date);
+    assert_eq!(test.session_id(), 2);
+
+    // Keyed by bowler, date and session
+    let stored = to_bson(&test).unwrap();
+    let id = stored.as_document().unwrap().get_document("_id").unwrap();
+
+    assert_eq!(id.get_str("bowler").unwrap(), "Sam");
+    assert_eq!(id.get_i32("session").unwrap(), 2);
+    assert_eq!(from_bson::<Games>(stored).unwrap().key(), &key);
+}
+
+#[test]
+fn session() {
+    let date = Date::build_with(2024, 2, 5);
+    let mut test = Games::build_with(date, Game::build(1));
+
+    assert_eq!(test.session(), &Session::default());
+    assert_eq!(test.session().start(), None);
+    assert_eq!(test.session().label(), &SessionLabel::Practice);
+
+    let mut session = Session::build_with(
+        Time::build_with(18, 5),
+        SessionLabel::League,
+        Some("Main Street Lanes"),
+    );
+
+    assert_eq!(session.start().unwrap().to_string(), "18:05");
+    assert_eq!(session.location(), Some("Main Street Lanes"));
+
+    session.set_label(SessionLabel::Other("Scotch doubles".to_string()));
+    session.set_location(None);
+
+    assert_eq!(session.label().to_string(), "Scotch doubles");
+    assert_eq!(session.location(), None);
+
+    test.set_session(session.clone());
+
+    let stored = to_bson(&test).unwrap();
+    assert_eq!(from_bson::<Games>(stored).unwrap().session(), &session);
+
+    // Stored before sessions had any details
+    let mut doc = to_bson(&test).unwrap().as_document().unwrap().clone();
+    doc.remove("session");
+    assert_eq!(
+        from_bson::<Games>(Bson::Document(doc)).unwrap().session(),
+        &Session::default()
+    );
+}
+
+#[test]
+fn build_from_sessions() {
+    let date = Date::build_with(2024, 2, 5);
+    let bowler = Bowler::build("Sam");
+
+    let league = Games::build_for(
+        SessionKey::build(bowler.clone(), date, 1),
+        vec!["X X X X X X X X X XXX".parse::<Game>().unwrap()],
+    );
+
+    let mut practice_game = "9- 9- 9- 9- 9- 9- 9- 9- 9- 9-".parse::<Game>().unwrap();
+    *practice_game.game_num_mut() = 1;
+    let practice = Games::build_for(
+        SessionKey::build(bowler.clone(), date, 2),
+        vec![practice_game.clone(), practice_game],
+    );
+
+    let test = Games::build_from_sessions(&[league.clone(), practice.clone()]);
+
+    assert_eq!(test.bowler(), &bowler);
+    assert_eq!(test.date(), date);
+    assert_eq!(test.session_id(), SessionKey::ALL_SESSIONS);
+    assert_eq!(test.games().len(), 3);
+    // Renumbered across the day
+    assert!(test.is_valid());
+
+    assert_eq!(league.average(), 300.0);
+    assert_eq!(practice.average(), 90.0);
+    assert_eq!(test.average(), 160.0);
+}
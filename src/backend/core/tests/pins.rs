@@ -0,0 +1,145 @@
+use super::{Pins, Split};
+
+#[test]
+fn build() {
+    let test = Pins::build();
+
+    assert_eq!(test, Pins::ALL);
+    assert_eq!(test.standing(), 10);
+    assert_eq!(test.knocked(), 0);
+}
+
+#[test]
+fn build_with() {
+    let test = Pins::build_with(&[7, 10]).unwrap();
+
+    assert_eq!(test.bits(), 0b10_0100_0000);
+    assert_eq!(test.standing(), 2);
+    assert_eq!(test.knocked(), 8);
+    assert_eq!(test.pins(), vec![7, 10]);
+
+    assert_eq!(Pins::build_with(&[]).unwrap(), Pins::NONE);
+}
+
+#[test]
+fn build_with_fail() {
+    assert!(Pins::build_with(&[0]).is_none());
+    assert!(Pins::build_with(&[1, 11]).is_none());
+}
+
+#[test]
+fn from_bits() {
+    for bits in 0..=Pins::ALL.bits() {
+        let test = Pins::from_bits(bits).unwrap();
+
+        assert!(test.is_valid());
+        assert_eq!(test.bits(), bits);
+        assert_eq!(test.standing() as u32, bits.count_ones());
+    }
+
+    assert!(Pins::from_bits(Pins::ALL.bits() + 1).is_none());
+}
+
+#[test]
+fn is_standing() {
+    let test = Pins::build_with(&[1, 2, 4]).unwrap();
+
+    for pin in 0..=11 {
+        assert_eq!(test.is_standing(pin), [1, 2, 4].contains(&pin));
+    }
+}
+
+#[test]
+fn is_subset() {
+    let leave = Pins::build_with(&[2, 4, 5]).unwrap();
+    let after = Pins::build_with(&[4]).unwrap();
+    let other = Pins::build_with(&[4, 7]).unwrap();
+
+    assert!(after.is_subset(&leave));
+    assert!(!other.is_subset(&leave));
+    assert!(Pins::NONE.is_subset(&leave));
+    assert!(leave.is_subset(&Pins::ALL));
+}
+
+#[test]
+fn display() {
+    assert_eq!(Pins::build_with(&[7, 10]).unwrap().to_string(), "7-10");
+    assert_eq!(Pins::build_with(&[10]).unwrap().to_string(), "10");
+    assert_eq!(Pins::NONE.to_string(), "-");
+}
+
+#[test]
+fn is_split() {
+    let splits: [&[u8]; 10] = [
+        &[7, 10],
+        &[4, 6],
+        &[2, 7],
+        &[3, 10],
+        &[5, 6],
+        &[8, 9],
+        &[4, 6, 7, 10],
+        &[6, 7, 10],
+        &[4, 6, 7, 8, 10],
+        &[2, 3],
+    ];
+
+    for pins in splits {
+        let test = Pins::build_with(pins).unwrap();
+
+        assert!(test.is_split(), "{} should be a split", test);
+    }
+}
+
+#[test]
+fn is_split_not() {
+    let leaves: [&[u8]; 9] = [
+        &[],
+        &[10],
+        &[1, 7, 10],
+        &[2, 4, 5, 8],
+        &[3, 6, 10],
+        &[2, 8],
+        &[3, 9],
+        &[3, 5, 6],
+        &[4, 7, 8],
+    ];
+
+    for pins in leaves {
+        let test = Pins::build_with(pins).unwrap();
+
+        assert!(!test.is_split(), "{} should not be a split", test);
+    }
+
+    assert!(!Pins::ALL.is_split());
+}
+
+#[test]
+fn split() {
+    let named: [(&[u8], Split); 6] = [
+        (&[7, 10], Split::SevenTen),
+        (&[4, 6, 7, 10], Split::BigFour),
+        (&[6, 7, 10], Split::SixSevenTen),
+        (&[4, 6, 7, 8, 10], Split::GreekChurch),
+        (&[4, 6, 7, 9, 10], Split::GreekChurch),
+        (&[3, 10], Split::ThreeTen),
+    ];
+
+    for (pins, name) in named {
+        assert_eq!(Pins::build_with(pins).unwrap().split(), Some(name));
+    }
+
+    let other = Pins::build_with(&[4, 9]).unwrap();
+    assert_eq!(other.split(), Some(Split::Other(other)));
+
+    assert_eq!(Pins::build_with(&[2, 4, 5]).unwrap().split(), None);
+}
+
+#[test]
+fn split_display() {
+    assert_eq!(Split::SevenTen.to_string(), "7-10");
+    assert_eq!(Split::GreekChurch.to_string(), "Greek Church");
+    assert_eq!(
+        Split::Other(Pins::build_with(&[4, 9]).unwrap()).to_string(),
+        "4-9"
+    );
+}
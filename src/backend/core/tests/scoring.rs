@@ -0,0 +1,308 @@
+use super::{CurrentFrame, Frame, Game, Roll, RollErrorKind, RuleSet, Scorer, Traditional};
+
+fn rolls(pins: &[u8]) -> Vec<Roll> {
+    pins.iter().map(|p| Roll::build(*p)).collect()
+}
+
+#[test]
+fn build() {
+    let test = Scorer::build();
+
+    assert!(test.rolls().is_empty());
+    assert_eq!(test.next_ball(), Some((1, 1)));
+    assert_eq!(test.standing(), 10);
+    assert!(!test.is_complete());
+    assert_eq!(test.score(), 0);
+}
+
+#[test]
+fn add_roll() {
+    let mut test = Scorer::build();
+
+    test.add_roll(Roll::build(7)).unwrap();
+    assert_eq!(test.next_ball(), Some((1, 2)));
+    assert_eq!(test.standing(), 3);
+
+    test.add_roll(Roll::build(3)).unwrap();
+    assert_eq!(test.next_ball(), Some((2, 1)));
+    assert_eq!(test.standing(), 10);
+
+    test.add_roll(Roll::build(10)).unwrap();
+    assert_eq!(test.next_ball(), Some((3, 1)));
+
+    for _ in 3..=9 {
+        test.add_roll(Roll::build(10)).unwrap();
+    }
+
+    assert_eq!(test.next_ball(), Some((10, 1)));
+
+    test.add_roll(Roll::build(10)).unwrap();
+    assert_eq!(test.next_ball(), Some((10, 2)));
+    assert_eq!(test.standing(), 10);
+
+    test.add_roll(Roll::build(6)).unwrap();
+    assert_eq!(test.next_ball(), Some((10, 3)));
+    assert_eq!(test.standing(), 4);
+
+    test.add_roll(Roll::build(4)).unwrap();
+    assert_eq!(test.next_ball(), None);
+    assert!(test.is_complete());
+}
+
+#[test]
+fn add_roll_error() {
+    let mut test = Scorer::build();
+
+    test.add_roll(Roll::build(7)).unwrap();
+
+    let err = test.add_roll(Roll::build(4)).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::TooManyPins);
+    assert_eq!((err.frame(), err.ball()), (1, 2));
+
+    // Rejected rolls are not added
+    assert_eq!(test.rolls(), rolls(&[7]).as_slice());
+
+    let mut test = Scorer::build_with(&rolls(&[0; 20])).unwrap();
+
+    let err = test.add_roll(Roll::build(0)).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::GameComplete);
+
+    // No third ball on an open 10th frame
+    assert!(Scorer::build_with(&rolls(&[0; 21])).is_err());
+    assert!(Scorer::build_with(&rolls(&[11])).is_err());
+}
+
+#[test]
+fn score() {
+    assert_eq!(Scorer::build_with(&rolls(&[10; 12])).unwrap().score(), 300);
+    assert_eq!(Scorer::build_with(&rolls(&[5; 21])).unwrap().score(), 150);
+    assert_eq!(Scorer::build_with(&rolls(&[4; 20])).unwrap().score(), 80);
+
+    // Bonuses only count the balls thrown so far
+    assert_eq!(Scorer::build_with(&rolls(&[10, 10])).unwrap().score(), 30);
+    assert_eq!(Scorer::build_with(&rolls(&[9, 1, 5])).unwrap().score(), 20);
+}
+
+#[test]
+fn frame_scores() {
+    let test = Scorer::build_with(&rolls(&[10, 9, 1, 8, 0, 10, 10])).unwrap();
+
+    assert_eq!(
+        test.frame_scores(),
+        vec![
+            Some(20),
+            Some(38),
+            Some(46),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None
+        ]
+    );
+    assert_eq!(test.score_n(3), Some(46));
+    assert_eq!(test.score_n(4), None);
+    assert_eq!(test.score_n(0), Some(0));
+    assert_eq!(test.score_n(11), None);
+}
+
+#[test]
+fn frame_scores_tenth() {
+    // Spare in the 9th followed by a strike in the 10th
+    let mut pins = vec![0; 16];
+    pins.extend([9, 1, 10, 9, 1]);
+
+    let test = Scorer::build_with(&rolls(&pins)).unwrap();
+
+    assert_eq!(test.score_n(8), Some(0));
+    assert_eq!(test.score_n(9), Some(20));
+    assert_eq!(test.score_n(10), Some(40));
+    assert_eq!(test.score(), 40);
+
+    let test = Scorer::build_with(&rolls(&pins[..pins.len() - 1])).unwrap();
+
+    assert_eq!(test.score_n(9), Some(20));
+    assert_eq!(test.score_n(10), None);
+}
+
+#[test]
+fn build_from_frames() {
+    let game = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+
+    let test = Scorer::build_from_frames(game.frames()).unwrap();
+
+    assert!(test.is_complete());
+    assert_eq!(test.rolls().len(), 16);
+    assert_eq!(test.frames(), game.frames());
+    assert_eq!(test.score(), game.score());
+
+    // Stops at the first frame that is not played
+    let mut frames = game.frames().to_vec();
+    frames[3] = Frame::Uninit;
+
+    let test = Scorer::build_from_frames(&frames).unwrap();
+
+    assert_eq!(test.rolls().len(), 5);
+    assert_eq!(test.next_ball(), Some((4, 1)));
+}
+
+#[test]
+fn add_frames_error() {
+    let game = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+
+    // A ball after a strike would be the first ball of the next frame
+    let mut frames = game.frames().to_vec();
+    frames[2] = Frame::TwoFrame(10, 4);
+
+    let mut test = Scorer::build();
+    let err = test.add_frames(&frames).unwrap_err();
+
+    assert_eq!(err.kind(), RollErrorKind::FrameOverrun);
+    assert_eq!((err.frame(), err.ball()), (3, 2));
+    // Only the frames before it are added
+    assert_eq!(test.next_ball(), Some((3, 1)));
+
+    let mut with_overrun = game.clone();
+    with_overrun.frames_mut()[2] = Frame::TwoFrame(10, 4);
+
+    assert!(!with_overrun.is_valid_with(Traditional));
+    assert_eq!(with_overrun.score(), 20 + 10);
+    assert_eq!(
+        with_overrun.try_score().unwrap_err().kind(),
+        RollErrorKind::FrameOverrun
+    );
+    assert_eq!(game.try_score(), Ok(game.score()));
+
+    // Fill balls are owed after a strike in the 10th
+    frames = game.frames().to_vec();
+    frames[9] = Frame::TwoFrame(10, 5);
+
+    let err = Scorer::build_from_frames(&frames).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::FrameIncomplete);
+    assert_eq!((err.frame(), err.ball()), (10, 2));
+
+    frames[9] = Frame::TwoFrame(7, 4);
+    let err = Scorer::build_from_frames(&frames).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::TooManyPins);
+}
+
+#[test]
+fn frames() {
+    let test = Scorer::build_with(&rolls(&[10, 9, 1, 8])).unwrap();
+
+    let frames = test.frames();
+
+    assert_eq!(frames[0], Frame::TwoFrame(10, 0));
+    assert_eq!(frames[1], Frame::TwoFrame(9, 1));
+    // Unfinished frame
+    assert_eq!(frames[2], Frame::Uninit);
+    assert_eq!(frames.len(), 10);
+}
+
+#[test]
+fn game_build_from_rolls() {
+    let test = Game::build_from_rolls(2, &rolls(&[10; 12])).unwrap();
+
+    assert_eq!(test.game_num(), 2);
+    assert!(test.is_valid());
+    assert_eq!(test.score(), 300);
+    assert_eq!(test.rolls(), rolls(&[10; 12]));
+
+    assert!(Game::build_from_rolls(1, &rolls(&[7, 7])).is_err());
+}
+
+#[test]
+fn current_frame_score() {
+    // Strike is 30, spare is 10 plus the first ball, no bonus balls
+    let test = Scorer::build_with_rolls(CurrentFrame, &rolls(&[10, 7, 3, 8, 1])).unwrap();
+
+    assert_eq!(test.score_n(1), Some(30));
+    assert_eq!(test.score_n(2), Some(47));
+    assert_eq!(test.score_n(3), Some(56));
+    assert_eq!(test.score(), 56);
+
+    let test = Scorer::build_with_rolls(CurrentFrame, &rolls(&[10; 10])).unwrap();
+
+    assert!(test.is_complete());
+    assert_eq!(test.score(), 300);
+}
+
+#[test]
+fn current_frame_tenth() {
+    // No fill balls after a strike or spare in the 10th
+    let mut pins = vec![0; 18];
+    pins.push(10);
+
+    let mut test = Scorer::build_with_rolls(CurrentFrame, &rolls(&pins)).unwrap();
+
+    assert!(test.is_complete());
+    assert_eq!(test.score(), 30);
+    assert!(test.add_roll(Roll::build(10)).is_err());
+
+    pins.pop();
+    pins.extend([6, 4]);
+
+    let test = Scorer::build_with_rolls(CurrentFrame, &rolls(&pins)).unwrap();
+
+    assert!(test.is_complete());
+    assert_eq!(test.score(), 16);
+}
+
+#[test]
+fn game_score_with() {
+    let game = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+
+    assert_eq!(game.score_with(Traditional), game.score());
+    assert!(!game.is_valid_with(CurrentFrame));
+
+    // Strike in the 10th without fill balls
+    let mut game = game;
+    game.frames_mut()[9] = Frame::TwoFrame(10, 0);
+
+    assert!(game.is_valid_with(CurrentFrame));
+    assert!(!game.is_valid());
+    assert_eq!(
+        game.score_with(CurrentFrame),
+        30 + 19 + 8 + 90 + 17 + 9 + 60
+    );
+    assert_eq!(game.score_n_with(CurrentFrame, 2), Some(49));
+    assert_eq!(
+        game.score_with(RuleSet::CurrentFrame),
+        game.score_with(CurrentFrame)
+    );
+}
+
+#[test]
+fn perfect_game() {
+    let game = "X X X X X X X X X XXX".parse::<Game>().unwrap();
+
+    for rules in [RuleSet::Traditional, RuleSet::CurrentFrame] {
+        assert_eq!(game.score_with(rules), 300, "{}", rules);
+        assert_eq!(game.score_n_with(rules, 10), Some(300), "{}", rules);
+    }
+
+    // The fill balls are not part of a current frame game
+    assert!(game.is_valid_with(RuleSet::Traditional));
+    assert!(!game.is_valid_with(RuleSet::CurrentFrame));
+
+    let err = game.try_score_with(RuleSet::CurrentFrame).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::GameComplete);
+    assert_eq!((err.frame(), err.ball()), (10, 2));
+
+    let mut game = game;
+    game.frames_mut()[9] = Frame::TwoFrame(10, 0);
+
+    assert!(game.is_valid_with(RuleSet::CurrentFrame));
+    assert_eq!(game.try_score_with(RuleSet::CurrentFrame), Ok(300));
+}
+
+#[test]
+fn possible_score_with() {
+    let mut game = Game::build(1);
+    game.frames_mut()[0] = Frame::TwoFrame(7, 3);
+
+    assert_eq!(game.possible_score_with(CurrentFrame), 17 + 9 * 30);
+    assert_eq!(game.guaranteed_score_with(CurrentFrame), 17);
+}
@@ -0,0 +1,270 @@
+use mongodb::bson::{doc, from_bson, to_bson, Bson};
+
+use super::{
+    CurrentFrame, Deadwood, Frame, Game, Roll, RollErrorKind, Scorer, ScoringRules, Traditional,
+    Variant,
+};
+
+fn rolls(pins: &[u8]) -> Vec<Roll> {
+    pins.iter().map(|p| Roll::build(*p)).collect()
+}
+
+// Strike, open three ball frame, spare, ten-box, five gutter frames and a perfect 10th
+fn duckpin_game() -> Game {
+    let mut pins = vec![10, 3, 4, 2, 6, 4, 2, 3, 5];
+    pins.extend([0; 15]);
+    pins.extend([10, 10, 10]);
+
+    Game::build_from_rolls_with(1, Variant::Duckpin, &rolls(&pins)).unwrap()
+}
+
+#[test]
+fn getters() {
+    assert_eq!(Variant::default(), Variant::TenPin);
+
+    assert_eq!(Variant::TenPin.rack_value(), 10);
+    assert_eq!(Variant::TenPin.balls(), 2);
+    assert_eq!(Variant::Duckpin.balls(), 3);
+    assert_eq!(Variant::FivePin.pin_values().len(), 5);
+    assert_eq!(Variant::FivePin.rack_value(), 15);
+
+    assert_eq!(Variant::TenPin.max_score(), 300);
+    assert_eq!(Variant::Candlepin.max_score(), 300);
+    assert_eq!(Variant::FivePin.max_score(), 450);
+}
+
+#[test]
+fn frame_is_valid_no_with() {
+    let open = Frame::ThreeFrame(3, 4, 2);
+
+    assert!(open.is_valid_no_with(Variant::Duckpin, 1));
+    assert!(!open.is_valid_no_with(Variant::TenPin, 1));
+
+    // Third ball still to come
+    assert!(!Frame::TwoFrame(3, 4).is_valid_no_with(Variant::Duckpin, 1));
+    assert!(Frame::TwoFrame(3, 7).is_valid_no_with(Variant::Duckpin, 1));
+    assert!(Frame::TwoFrame(10, 0).is_valid_no_with(Variant::Candlepin, 1));
+    assert!(!Frame::ThreeFrame(10, 0, 0).is_valid_no_with(Variant::Duckpin, 1));
+    assert!(!Frame::ThreeFrame(6, 6, 0).is_valid_no_with(Variant::Duckpin, 1));
+
+    // 10th frame is always three balls
+    assert!(Frame::ThreeFrame(3, 4, 2).is_valid_no_with(Variant::Duckpin, 10));
+    assert!(Frame::ThreeFrame(10, 10, 10).is_valid_no_with(Variant::Duckpin, 10));
+    assert!(!Frame::TwoFrame(3, 7).is_valid_no_with(Variant::Duckpin, 10));
+
+    assert!(Frame::TwoFrame(15, 0).is_valid_no_with(Variant::FivePin, 1));
+    assert!(Frame::ThreeFrame(5, 3, 7).is_valid_no_with(Variant::FivePin, 1));
+    assert!(!Frame::ThreeFrame(5, 3, 8).is_valid_no_with(Variant::FivePin, 1));
+    assert!(!Frame::TwoFrame(10, 0).is_valid_no_with(Variant::FivePin, 1));
+
+    // Five-pin counts have to be made of 2-3-5-3-2 pins
+    assert!(!Frame::ThreeFrame(1, 0, 0).is_valid_no_with(Variant::FivePin, 1));
+    assert!(!Frame::ThreeFrame(14, 0, 0).is_valid_no_with(Variant::FivePin, 1));
+    assert!(!Frame::ThreeFrame(5, 9, 0).is_valid_no_with(Variant::FivePin, 1));
+    assert!(Frame::ThreeFrame(5, 8, 0).is_valid_no_with(Variant::FivePin, 1));
+    assert!(!Frame::ThreeFrame(15, 15, 1).is_valid_no_with(Variant::FivePin, 10));
+}
+
+#[test]
+fn fits_rack() {
+    assert!(Variant::TenPin.fits_rack(&[9, 1]));
+    assert!(!Variant::TenPin.fits_rack(&[9, 2]));
+
+    assert!(Variant::FivePin.fits_rack(&[]));
+    assert!(Variant::FivePin.fits_rack(&[0, 15]));
+    assert!(!Variant::FivePin.fits_rack(&[1]));
+    assert!(!Variant::FivePin.fits_rack(&[14]));
+    // 5 is the head pin or a 2 and a 3
+    assert!(Variant::FivePin.fits_rack(&[5, 10]));
+    assert!(Variant::FivePin.fits_rack(&[5, 5, 5]));
+    // Both 2s down leaves 3-5-3, none of which make 10
+    assert!(!Variant::FivePin.fits_rack(&[2, 2, 10]));
+}
+
+#[test]
+fn frame_symbols() {
+    assert_eq!(Frame::TwoFrame(15, 0).symbols(Variant::FivePin), ["X"]);
+    assert_eq!(
+        Frame::ThreeFrame(5, 3, 7).symbols(Variant::FivePin),
+        ["5", "3", "/"]
+    );
+    assert_eq!(
+        Frame::ThreeFrame(0, 4, 2).symbols(Variant::Duckpin),
+        ["-", "4", "2"]
+    );
+    // Ten-pin notation is unchanged
+    assert_eq!(Frame::TwoFrame(9, 1).symbols(Variant::TenPin), ["9", "/"]);
+}
+
+#[test]
+fn duckpin_score() {
+    let game = duckpin_game();
+
+    assert!(game.is_valid());
+    assert_eq!(game.variant(), Variant::Duckpin);
+    assert_eq!(game.frames()[1], Frame::ThreeFrame(3, 4, 2));
+
+    assert_eq!(game.score_n(1), Some(17));
+    assert_eq!(game.score_n(2), Some(26));
+    assert_eq!(game.score_n(3), Some(38));
+    // No bonus for a ten-box
+    assert_eq!(game.score_n(4), Some(48));
+    assert_eq!(game.score(), 78);
+
+    let perfect = Game::build_from_rolls_with(1, Variant::Candlepin, &rolls(&[10; 12])).unwrap();
+
+    assert_eq!(perfect.score(), 300);
+}
+
+#[test]
+fn duckpin_statistics() {
+    let game = duckpin_game();
+
+    assert_eq!(game.num_strikes(), 4);
+    assert_eq!(game.num_spares(), 1);
+    assert_eq!(game.strike_chances(), 12);
+    assert_eq!(game.spare_chances(), 8);
+    assert_eq!(game.open_frames(), 7);
+    assert_eq!(game.clean_frames(), 3);
+}
+
+#[test]
+fn candlepin_deadwood() {
+    let mut game = Game::build_from_rolls_with(1, Variant::Candlepin, &rolls(&[3; 30])).unwrap();
+    assert!(game.is_valid());
+
+    // Wood from the first ball knocks down one of the three pins of the second
+    game.set_deadwood(1, 2, 1);
+    assert!(game.is_valid());
+    assert!(game.is_valid_with(Traditional));
+    assert_eq!(game.deadwood_on(1, 2), 1);
+    assert_eq!(game.deadwood_pins(), 1);
+    // The pins count towards the ball like any other
+    assert_eq!(game.score(), 90);
+
+    game.set_deadwood(2, 3, 3);
+    assert_eq!(game.deadwood_pins(), 4);
+    assert!(game.is_valid());
+
+    // Cleared again
+    game.set_deadwood(2, 3, 0);
+    assert_eq!(game.deadwood(), [Deadwood::build(1, 2, 1)]);
+
+    // Nothing lies on the lane before the first ball of a rack
+    let mut first_ball = game.clone();
+    first_ball.set_deadwood(1, 1, 1);
+    assert!(!first_ball.is_valid());
+
+    // More than the ball counted
+    let mut too_many = game.clone();
+    too_many.set_deadwood(1, 3, 4);
+    assert!(!too_many.is_valid());
+
+    // A ball that was not thrown
+    let mut no_ball = game.clone();
+    no_ball.set_deadwood(1, 4, 1);
+    assert!(!no_ball.is_valid());
+
+    // Duckpin clears the fallen pins between balls
+    let mut duckpin = duckpin_game();
+    duckpin.set_deadwood(2, 2, 1);
+    assert!(!duckpin.is_valid());
+    assert!(!Variant::Duckpin.has_deadwood());
+    assert!(Variant::Candlepin.has_deadwood());
+
+    // Listed under the card
+    assert!(game.scorecard().to_string().ends_with("Deadwood: 1.2 (1)"));
+}
+
+#[test]
+fn five_pin_score() {
+    let perfect = Game::build_from_rolls_with(1, Variant::FivePin, &rolls(&[15; 12])).unwrap();
+
+    assert!(perfect.is_valid());
+    assert_eq!(perfect.score(), 450);
+
+    let mut pins = vec![15; 9];
+    pins.extend([5, 10, 15]);
+
+    let game = Game::build_from_rolls_with(1, Variant::FivePin, &rolls(&pins)).unwrap();
+
+    assert!(game.is_valid());
+    assert_eq!(game.score_n(9), Some(380));
+    assert_eq!(game.score(), 410);
+    assert_eq!(game.possible_score(), 410);
+
+    let mut test = Scorer::build_with_variant(Variant::FivePin, CurrentFrame);
+    test.add_roll(Roll::build(15)).unwrap();
+
+    assert_eq!(test.score(), 45);
+
+    // No pins are worth one
+    let err = Game::build_from_rolls_with(1, Variant::FivePin, &rolls(&[1; 30])).unwrap_err();
+    assert_eq!(err.kind(), RollErrorKind::NoSuchCount);
+
+    let mut ones = Game::build_with_variant(1, Variant::FivePin);
+    ones.frames_mut().fill(Frame::ThreeFrame(1, 1, 1));
+
+    assert!(!ones.is_valid());
+    assert!(!ones.is_valid_with(Traditional));
+    assert_eq!(ones.score(), 0);
+}
+
+#[test]
+fn possible_score() {
+    let mut game = Game::build_with_variant(1, Variant::Duckpin);
+    game.frames_mut()[0] = Frame::ThreeFrame(3, 4, 2);
+
+    assert_eq!(game.possible_score(), 9 + 270);
+    assert_eq!(game.guaranteed_score(), 9);
+}
+
+// Traditional bonuses with every frame counted twice
+#[derive(Copy, Clone)]
+struct Doubled;
+
+impl ScoringRules for Doubled {
+    fn bonus_balls(&self, variant: Variant, frame: &[Roll]) -> usize {
+        Traditional.bonus_balls(variant, frame)
+    }
+
+    fn frame_score(&self, variant: Variant, frame: &[Roll], bonus: &[Roll]) -> u16 {
+        Traditional.frame_score(variant, frame, bonus) * 2
+    }
+
+    fn fill_balls(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn max_score() {
+    let perfect = Game::build_from_rolls_with(1, Variant::Duckpin, &rolls(&[10; 12])).unwrap();
+    assert!(perfect.is_valid_with(Traditional));
+
+    // No rule set can score past the most a variant allows
+    let open = Game::build_from_rolls_with(1, Variant::Duckpin, &rolls(&[1; 30])).unwrap();
+    assert!(open.is_valid_with(Doubled));
+    assert!(!perfect.is_valid_with(Doubled));
+}
+
+#[test]
+fn bson() {
+    let game = duckpin_game();
+
+    let stored = to_bson(&game).unwrap();
+    assert_eq!(from_bson::<Game>(stored).unwrap(), game);
+
+    // Ten-pin games are stored as before
+    let stored = to_bson(&Game::build(1)).unwrap();
+    assert!(!stored.as_document().unwrap().contains_key("variant"));
+
+    let stored = doc! {
+        "game_num": 1,
+        "frames": to_bson(&Game::build(1).frames()).unwrap(),
+    };
+    assert_eq!(
+        from_bson::<Game>(Bson::Document(stored)).unwrap().variant(),
+        Variant::TenPin
+    );
+}
@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
 
-use chrono::{Datelike, Local, NaiveDate};
-use itertools::{izip, Itertools};
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, Timelike};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::scoring::{Roll, RollError, RuleSet, Scorer, ScoringRules, Traditional};
+use super::variant::Variant;
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Date {
     year: u16,
@@ -85,12 +88,364 @@ impl std::fmt::Display for Date {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    // Constructor
+    pub fn build() -> Self {
+        Self::from(Local::now().time())
+    }
+
+    pub fn build_with(hour: u8, minute: u8) -> Self {
+        Self { hour, minute }
+    }
+
+    // Getter
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+}
+
+impl std::convert::From<NaiveTime> for Time {
+    fn from(value: NaiveTime) -> Self {
+        Time {
+            hour: value.hour() as u8,
+            minute: value.minute() as u8,
+        }
+    }
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:0>2}:{:0>2}", self.hour, self.minute)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionLabel {
+    League,
+    #[default]
+    Practice,
+    Tournament,
+    Other(String),
+}
+
+impl std::fmt::Display for SessionLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::League => write!(f, "League"),
+            Self::Practice => write!(f, "Practice"),
+            Self::Tournament => write!(f, "Tournament"),
+            Self::Other(label) => write!(f, "{}", label),
+        }
+    }
+}
+
+// When, why and where a set of games was bowled
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    start: Option<Time>,
+    label: SessionLabel,
+    location: Option<String>,
+}
+
+impl Session {
+    // Constructor
+    pub fn build() -> Self {
+        Self::build_with(Time::build(), SessionLabel::default(), None)
+    }
+
+    pub fn build_with(start: Time, label: SessionLabel, location: Option<&str>) -> Self {
+        Self {
+            start: Some(start),
+            label,
+            location: location.map(str::to_string),
+        }
+    }
+
+    // Getter
+    pub fn start(&self) -> Option<Time> {
+        self.start
+    }
+
+    pub fn label(&self) -> &SessionLabel {
+        &self.label
+    }
+
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    // Setter
+    pub fn set_start(&mut self, start: Option<Time>) {
+        self.start = start;
+    }
+
+    pub fn set_label(&mut self, label: SessionLabel) {
+        self.label = label;
+    }
+
+    pub fn set_location(&mut self, location: Option<&str>) {
+        self.location = location.map(str::to_string);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Bowler {
+    name: String,
+}
+
+impl Bowler {
+    // Owner of games stored before bowlers were tracked
+    pub const DEFAULT_NAME: &'static str = "Default";
+
+    // Constructor
+    pub fn build(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    // Getter
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Default for Bowler {
+    fn default() -> Self {
+        Self::build(Self::DEFAULT_NAME)
+    }
+}
+
+impl std::fmt::Display for Bowler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// Who bowled a set of games, on which day, and in which session of that day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionKey {
+    bowler: Bowler,
+    date: Date,
+    session: u8,
+}
+
+impl SessionKey {
+    // Session number standing for every session of the day
+    pub const ALL_SESSIONS: u8 = 0;
+
+    // Constructor
+    pub fn build(bowler: Bowler, date: Date, session: u8) -> Self {
+        Self {
+            bowler,
+            date,
+            session,
+        }
+    }
+
+    // Getter
+    pub fn bowler(&self) -> &Bowler {
+        &self.bowler
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn session_id(&self) -> u8 {
+        self.session
+    }
+}
+
+// Set of pins left standing, bit n - 1 is set when pin n is standing
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pins(u16);
+
+impl Pins {
+    pub const NONE: Pins = Pins(0);
+    pub const ALL: Pins = Pins(0b11_1111_1111);
+
+    // Constructor
+    pub fn build() -> Self {
+        Self::ALL
+    }
+
+    pub fn build_with(pins: &[u8]) -> Option<Self> {
+        pins.iter().try_fold(Self::NONE, |acc, pin| match pin {
+            1..=10 => Some(Self(acc.0 | 1 << (pin - 1))),
+            _ => None,
+        })
+    }
+
+    pub fn from_bits(bits: u16) -> Option<Self> {
+        if bits & !Self::ALL.0 != 0 {
+            return None;
+        }
+
+        Some(Self(bits))
+    }
+
+    // Getter
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    // Method
+    pub fn is_valid(&self) -> bool {
+        self.0 & !Self::ALL.0 == 0
+    }
+
+    pub fn is_standing(&self, pin: u8) -> bool {
+        (1..=10).contains(&pin) && self.0 & 1 << (pin - 1) != 0
+    }
+
+    pub fn is_subset(&self, other: &Pins) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn standing(&self) -> u8 {
+        (self.0 & Self::ALL.0).count_ones() as u8
+    }
+
+    pub fn knocked(&self) -> u8 {
+        10 - self.standing()
+    }
+
+    pub fn pins(&self) -> Vec<u8> {
+        (1..=10).filter(|pin| self.is_standing(*pin)).collect()
+    }
+
+    // Headpin down and the standing pins split into separate groups
+    pub fn is_split(&self) -> bool {
+        if self.is_standing(1) || self.standing() < 2 {
+            return false;
+        }
+
+        // Pins in the same row only touch when the pin in front of them is standing,
+        // sleepers (2-8, 3-9) are hit straight through and never split
+        let touching = |p1: u8, p2: u8| match (p1.min(p2), p1.max(p2)) {
+            (2, 4) | (2, 5) | (3, 5) | (3, 6) => true,
+            (4, 7) | (4, 8) | (5, 8) | (5, 9) | (6, 9) | (6, 10) => true,
+            (2, 8) | (3, 9) => true,
+            (4, 5) | (5, 6) => self.is_standing(p1.min(p2) - 2),
+            (7, 8) | (8, 9) | (9, 10) => self.is_standing(p1.min(p2) - 3),
+            _ => false,
+        };
+
+        let pins = self.pins();
+        let mut group = vec![pins[0]];
+        let mut i = 0;
+
+        while i < group.len() {
+            for pin in pins.iter() {
+                if !group.contains(pin) && touching(group[i], *pin) {
+                    group.push(*pin);
+                }
+            }
+
+            i += 1;
+        }
+
+        group.len() != pins.len()
+    }
+
+    pub fn split(&self) -> Option<Split> {
+        if !self.is_split() {
+            return None;
+        }
+
+        Some(match self.pins().as_slice() {
+            [7, 10] => Split::SevenTen,
+            [7, 9] => Split::SevenNine,
+            [8, 10] => Split::EightTen,
+            [4, 6] => Split::FourSix,
+            [2, 7] => Split::TwoSeven,
+            [3, 10] => Split::ThreeTen,
+            [5, 7] => Split::FiveSeven,
+            [5, 10] => Split::FiveTen,
+            [4, 7, 10] => Split::FourSevenTen,
+            [6, 7, 10] => Split::SixSevenTen,
+            [4, 6, 7, 10] => Split::BigFour,
+            [4, 6, 7, 8, 10] | [4, 6, 7, 9, 10] => Split::GreekChurch,
+            _ => Split::Other(*self),
+        })
+    }
+}
+
+impl Default for Pins {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl std::fmt::Display for Pins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == Self::NONE {
+            return write!(f, "-");
+        }
+
+        write!(f, "{}", self.pins().iter().join("-"))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Split {
+    SevenTen,
+    SevenNine,
+    EightTen,
+    FourSix,
+    TwoSeven,
+    ThreeTen,
+    FiveSeven,
+    FiveTen,
+    FourSevenTen,
+    SixSevenTen,
+    BigFour,
+    GreekChurch,
+    Other(Pins),
+}
+
+impl std::fmt::Display for Split {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SevenTen => write!(f, "7-10"),
+            Self::SevenNine => write!(f, "7-9"),
+            Self::EightTen => write!(f, "8-10"),
+            Self::FourSix => write!(f, "4-6"),
+            Self::TwoSeven => write!(f, "2-7"),
+            Self::ThreeTen => write!(f, "3-10"),
+            Self::FiveSeven => write!(f, "5-7"),
+            Self::FiveTen => write!(f, "5-10"),
+            Self::FourSevenTen => write!(f, "4-7-10"),
+            Self::SixSevenTen => write!(f, "6-7-10"),
+            Self::BigFour => write!(f, "4-6-7-10"),
+            Self::GreekChurch => write!(f, "Greek Church"),
+            Self::Other(pins) => write!(f, "{}", pins),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub enum Frame {
     #[default]
     Uninit,
     TwoFrame(u8, u8),
     ThreeFrame(u8, u8, u8),
+    // Pins left standing after each ball, the rack is only reset in the 10th frame
+    TwoLeave(Pins, Pins),
+    ThreeLeave(Pins, Pins, Pins),
 }
 
 impl Frame {
@@ -99,62 +454,273 @@ impl Frame {
         Self::default()
     }
 
+    // Getter
+    pub fn leaves(&self) -> Option<Vec<Pins>> {
+        match self {
+            Self::TwoLeave(l1, l2) => Some(vec![*l1, *l2]),
+            Self::ThreeLeave(l1, l2, l3) => Some(vec![*l1, *l2, *l3]),
+            _ => None,
+        }
+    }
+
+    pub fn first_leave(&self) -> Option<Pins> {
+        match self {
+            Self::TwoLeave(l1, _) | Self::ThreeLeave(l1, _, _) => Some(*l1),
+            _ => None,
+        }
+    }
+
     // Method
-    pub fn is_valid(&self) -> bool {
+    // Pin counts of the frame, leave frames are converted to the equivalent two or three ball frame
+    pub fn counts(&self) -> Frame {
         match self {
+            Self::TwoLeave(l1, l2) => {
+                Self::TwoFrame(l1.knocked(), l1.standing().saturating_sub(l2.standing()))
+            }
+            Self::ThreeLeave(l1, l2, l3) => {
+                let t2 = if *l1 == Pins::NONE {
+                    l2.knocked()
+                } else {
+                    l1.standing().saturating_sub(l2.standing())
+                };
+                let t3 = if *l2 == Pins::NONE {
+                    l3.knocked()
+                } else {
+                    l2.standing().saturating_sub(l3.standing())
+                };
+
+                Self::ThreeFrame(l1.knocked(), t2, t3)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    // Pin counts of each ball thrown, a strike before the 10th frame is a single ball
+    pub fn balls(&self) -> Vec<u8> {
+        self.balls_with(Variant::TenPin)
+    }
+
+    // Leaves are only recorded for racks of ten single count pins
+    pub fn balls_with(&self, variant: Variant) -> Vec<u8> {
+        if self.leaves().is_some() && variant.pin_values() != [1; 10] {
+            return Vec::new();
+        }
+
+        match self.counts() {
+            Self::TwoFrame(t1, 0) if t1 == variant.rack_value() => vec![t1],
+            Self::TwoFrame(t1, t2) => vec![t1, t2],
+            Self::ThreeFrame(t1, t2, t3) => vec![t1, t2, t3],
+            _ => Vec::new(),
+        }
+    }
+
+    // Ball number within its rack and whether it cleared the rack, for every ball thrown
+    fn rack_balls(&self, variant: Variant) -> Vec<(u8, bool)> {
+        let rack = variant.rack_value();
+        let mut standing = rack;
+        let mut ball = 0;
+
+        self.balls_with(variant)
+            .into_iter()
+            .map(|count| {
+                ball += 1;
+                standing = standing.saturating_sub(count);
+
+                let thrown = (ball, standing == 0);
+
+                if standing == 0 {
+                    standing = rack;
+                    ball = 0;
+                }
+
+                thrown
+            })
+            .collect()
+    }
+
+    // Equivalent count frame when `threshold` pins on the first ball of a rack is a strike
+    pub fn with_no_tap(&self, variant: Variant, threshold: u8) -> Frame {
+        let rack = variant.rack_value();
+        let mut standing = rack;
+        let mut fresh = true;
+
+        let mut no_tap = |count: u8| {
+            let count = if fresh && count >= threshold && count <= rack {
+                rack
+            } else {
+                count
+            };
+
+            standing = standing.saturating_sub(count);
+            fresh = standing == 0;
+
+            if fresh {
+                standing = rack;
+            }
+
+            count
+        };
+
+        match self.counts() {
+            Self::TwoFrame(t1, t2) => Self::TwoFrame(no_tap(t1), no_tap(t2)),
+            Self::ThreeFrame(t1, t2, t3) => Self::ThreeFrame(no_tap(t1), no_tap(t2), no_tap(t3)),
+            frame => frame,
+        }
+    }
+
+    // Notation of each ball, `X` and `/` mark a cleared rack
+    pub fn symbols(&self, variant: Variant) -> Vec<String> {
+        let counts = self.balls_with(variant);
+
+        counts
+            .iter()
+            .zip(self.rack_balls(variant))
+            .map(|(count, (ball, cleared))| match (ball, cleared) {
+                (1, true) => "X".to_string(),
+                (_, true) => "/".to_string(),
+                _ if *count == 0 => "-".to_string(),
+                _ => count.to_string(),
+            })
+            .collect()
+    }
+
+    // Pins can only be knocked down between balls unless the rack was cleared
+    fn is_valid_leaves(&self) -> bool {
+        let follows = |prev: &Pins, next: &Pins| *prev == Pins::NONE || next.is_subset(prev);
+
+        match self {
+            Self::TwoLeave(l1, l2) => l1.is_valid() && l2.is_valid() && l2.is_subset(l1),
+            Self::ThreeLeave(l1, l2, l3) => {
+                l1.is_valid()
+                    && l2.is_valid()
+                    && l3.is_valid()
+                    && follows(l1, l2)
+                    && follows(l2, l3)
+            }
+            _ => true,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        if !self.is_valid_leaves() {
+            return false;
+        }
+
+        match self.counts() {
             Self::Uninit => false,
-            Self::TwoFrame(t1, t2) => self.score() <= 10 && *t1 <= 10 && *t2 <= 10,
+            Self::TwoFrame(t1, t2) => self.score() <= 10 && t1 <= 10 && t2 <= 10,
             Self::ThreeFrame(t1, t2, t3) => {
-                if *t1 == 10 || *t1 + t2 == 10 {
-                    self.score() <= 30 && *t1 <= 10 && *t2 <= 10 && *t3 <= 10
+                if t1 == 10 || t1 + t2 == 10 {
+                    self.score() <= 30 && t1 <= 10 && t2 <= 10 && t3 <= 10
                 } else {
                     false
                 }
             }
+            Self::TwoLeave(..) | Self::ThreeLeave(..) => unreachable!(),
         }
     }
 
     pub fn is_valid_no(&self, no: u8) -> bool {
-        match self {
+        if !self.is_valid_leaves() {
+            return false;
+        }
+
+        match self.counts() {
             Self::Uninit => false,
             Self::TwoFrame(t1, t2) => {
                 if no != 10 {
-                    self.score() <= 10 && *t1 <= 10 && *t2 <= 10
+                    self.score() <= 10 && t1 <= 10 && t2 <= 10
                 } else {
-                    self.score() < 10 && *t1 <= 10 && *t2 <= 10
+                    self.score() < 10 && t1 <= 10 && t2 <= 10
                 }
             }
             Self::ThreeFrame(t1, t2, t3) => {
                 if no != 10 {
                     false
-                } else if *t1 == 10 || *t1 + t2 == 10 {
-                    self.score() <= 30 && *t1 <= 10 && *t2 <= 10 && *t3 <= 10
+                } else if t1 == 10 || t1 + t2 == 10 {
+                    self.score() <= 30 && t1 <= 10 && t2 <= 10 && t3 <= 10
                 } else {
                     false
                 }
             }
+            Self::TwoLeave(..) | Self::ThreeLeave(..) => unreachable!(),
+        }
+    }
+
+    // Every ball has to be on the lane and frames before the 10th end once the rack is cleared
+    pub fn is_valid_no_with(&self, variant: Variant, no: u8) -> bool {
+        if variant.is_ten_pin() {
+            return self.is_valid_no(no);
+        }
+
+        let balls = self.balls_with(variant);
+        let rack = variant.rack_value();
+        let mut standing = rack;
+        let mut counts = Vec::new();
+
+        if !self.is_valid_leaves() || balls.is_empty() || balls.len() > 3 {
+            return false;
+        }
+
+        for (n, count) in (1..).zip(balls.iter()) {
+            counts.push(*count);
+
+            if *count > standing || !variant.fits_rack(&counts) {
+                return false;
+            }
+
+            standing -= count;
+
+            if standing == 0 {
+                if no != 10 && n != balls.len() {
+                    return false;
+                }
+
+                standing = rack;
+                counts.clear();
+            }
+        }
+
+        if no == 10 {
+            balls.len() == 3
+        } else {
+            standing == rack || balls.len() == variant.balls() as usize
+        }
+    }
+
+    // Valid as some frame of a game in `variant`
+    pub fn is_valid_with(&self, variant: Variant) -> bool {
+        if variant.is_ten_pin() {
+            return self.is_valid();
         }
+
+        self.is_valid_no_with(variant, 1) || self.is_valid_no_with(variant, 10)
     }
 
     pub fn score(&self) -> u8 {
-        match self {
+        match self.counts() {
             Self::Uninit => 0,
             Self::TwoFrame(t1, t2) => t1 + t2,
             Self::ThreeFrame(t1, t2, t3) => t1 + t2 + t3,
+            Self::TwoLeave(..) | Self::ThreeLeave(..) => unreachable!(),
         }
     }
 
     pub fn is_strike(&self) -> bool {
-        matches!(self, Frame::TwoFrame(10, 0)) || matches!(self, Frame::ThreeFrame(10, _, _))
+        let counts = self.counts();
+
+        matches!(counts, Frame::TwoFrame(10, 0)) || matches!(counts, Frame::ThreeFrame(10, _, _))
     }
 
     pub fn is_spare(&self) -> bool {
-        matches!(self, Frame::TwoFrame(t1, t2) if (*t1 != 10) && t1 + t2 == 10)
-            || matches!(self, Frame::ThreeFrame(t1, t2, _) if (*t1 != 10) && t1 + t2 == 10)
+        let counts = self.counts();
+
+        matches!(counts, Frame::TwoFrame(t1, t2) if (t1 != 10) && t1 + t2 == 10)
+            || matches!(counts, Frame::ThreeFrame(t1, t2, _) if (t1 != 10) && t1 + t2 == 10)
     }
 
     pub fn num_strikes(&self) -> u8 {
-        match self {
+        match self.counts() {
             Self::TwoFrame(10, 0) => 1,
             Self::ThreeFrame(10, 10, 10) => 3,
             Self::ThreeFrame(10, 10, _) => 2,
@@ -165,33 +731,141 @@ impl Frame {
     }
 
     pub fn num_spares(&self) -> u8 {
-        match self {
-            Self::TwoFrame(t1, t2) if *t1 != 10 && t1 + t2 == 10 => 1,
-            Self::ThreeFrame(10, t2, t3) if *t2 != 10 && t2 + t3 == 10 => 1,
-            Self::ThreeFrame(t1, t2, _) if *t1 != 10 && t1 + t2 == 10 => 1,
+        match self.counts() {
+            Self::TwoFrame(t1, t2) if t1 != 10 && t1 + t2 == 10 => 1,
+            Self::ThreeFrame(10, t2, t3) if t2 != 10 && t2 + t3 == 10 => 1,
+            Self::ThreeFrame(t1, t2, _) if t1 != 10 && t1 + t2 == 10 => 1,
             _ => 0,
         }
     }
 
     pub fn strike_chances(&self) -> u8 {
-        match self {
+        match self.counts() {
             Self::Uninit => 0,
             Self::TwoFrame(..) => 1,
             Self::ThreeFrame(10, 10, _) => 3,
             Self::ThreeFrame(10, _, _) => 2,
             Self::ThreeFrame(_, _, _) => 1,
+            Self::TwoLeave(..) | Self::ThreeLeave(..) => unreachable!(),
         }
     }
 
     pub fn spare_chances(&self) -> u8 {
-        match self {
+        match self.counts() {
             Self::Uninit => 0,
-            Self::TwoFrame(t1, _) if *t1 != 10 => 1,
+            Self::TwoFrame(t1, _) if t1 != 10 => 1,
             Self::TwoFrame(_, _) => 0,
             Self::ThreeFrame(10, 10, _) => 0,
-            Self::ThreeFrame(10, t2, _) if *t2 != 10 => 1,
-            Self::ThreeFrame(t1, _, 10) if *t1 != 10 => 1,
+            Self::ThreeFrame(10, t2, _) if t2 != 10 => 1,
+            Self::ThreeFrame(t1, _, 10) if t1 != 10 => 1,
             Self::ThreeFrame(_, _, _) => 1,
+            Self::TwoLeave(..) | Self::ThreeLeave(..) => unreachable!(),
+        }
+    }
+
+    pub fn is_strike_with(&self, variant: Variant) -> bool {
+        if variant.is_ten_pin() {
+            return self.is_strike();
+        }
+
+        self.rack_balls(variant).first() == Some(&(1, true))
+    }
+
+    pub fn is_spare_with(&self, variant: Variant) -> bool {
+        if variant.is_ten_pin() {
+            return self.is_spare();
+        }
+
+        self.rack_balls(variant).get(1) == Some(&(2, true))
+    }
+
+    pub fn num_strikes_with(&self, variant: Variant) -> u8 {
+        if variant.is_ten_pin() {
+            return self.num_strikes();
+        }
+
+        self.rack_balls(variant)
+            .iter()
+            .filter(|b| **b == (1, true))
+            .count() as u8
+    }
+
+    pub fn num_spares_with(&self, variant: Variant) -> u8 {
+        if variant.is_ten_pin() {
+            return self.num_spares();
+        }
+
+        self.rack_balls(variant)
+            .iter()
+            .filter(|b| **b == (2, true))
+            .count() as u8
+    }
+
+    pub fn strike_chances_with(&self, variant: Variant) -> u8 {
+        if variant.is_ten_pin() {
+            return self.strike_chances();
+        }
+
+        self.rack_balls(variant)
+            .iter()
+            .filter(|(ball, _)| *ball == 1)
+            .count() as u8
+    }
+
+    pub fn spare_chances_with(&self, variant: Variant) -> u8 {
+        if variant.is_ten_pin() {
+            return self.spare_chances();
+        }
+
+        self.rack_balls(variant)
+            .iter()
+            .filter(|(ball, _)| *ball == 2)
+            .count() as u8
+    }
+
+    // Any rack of the frame, the 10th frame can leave a split after a strike
+    pub fn is_split(&self) -> bool {
+        self.num_splits() > 0
+    }
+
+    pub fn split(&self) -> Option<Split> {
+        self.split_racks()
+            .iter()
+            .find_map(|(leave, _)| leave.split())
+    }
+
+    pub fn is_split_converted(&self) -> bool {
+        self.num_converted_splits() > 0
+    }
+
+    pub fn num_splits(&self) -> u8 {
+        self.split_racks()
+            .iter()
+            .filter(|(leave, _)| leave.is_split())
+            .count() as u8
+    }
+
+    pub fn num_converted_splits(&self) -> u8 {
+        self.split_racks()
+            .iter()
+            .filter(|(leave, next)| leave.is_split() && *next == Pins::NONE)
+            .count() as u8
+    }
+
+    // Racks whose first ball was not a strike and had a ball thrown after it
+    pub fn split_chances(&self) -> u8 {
+        self.split_racks().len() as u8
+    }
+
+    // Leave of the first ball of each such rack with the leave of the ball after it. In the 10th a
+    // strike racks the pins again for the second ball, a fill ball has nothing thrown after it
+    fn split_racks(&self) -> Vec<(Pins, Pins)> {
+        match *self {
+            Self::TwoLeave(l1, l2) | Self::ThreeLeave(l1, l2, _) if l1 != Pins::NONE => {
+                vec![(l1, l2)]
+            }
+            Self::ThreeLeave(_, l2, l3) if l2 != Pins::NONE => vec![(l2, l3)],
+            _ => Vec::new(),
         }
     }
 }
@@ -208,6 +882,18 @@ impl std::convert::From<(u8, u8, u8)> for Frame {
     }
 }
 
+impl std::convert::From<(Pins, Pins)> for Frame {
+    fn from(value: (Pins, Pins)) -> Self {
+        Self::TwoLeave(value.0, value.1)
+    }
+}
+
+impl std::convert::From<(Pins, Pins, Pins)> for Frame {
+    fn from(value: (Pins, Pins, Pins)) -> Self {
+        Self::ThreeLeave(value.0, value.1, value.2)
+    }
+}
+
 impl std::convert::From<Vec<u8>> for Frame {
     fn from(value: Vec<u8>) -> Self {
         match value.len() {
@@ -229,14 +915,241 @@ impl std::cmp::PartialEq for Frame {
             (Self::ThreeFrame(s_t1, s_t2, s_t3), Self::ThreeFrame(o_t1, o_t2, o_t3)) => {
                 s_t1 == o_t1 && s_t2 == o_t2 && s_t3 == o_t3
             }
+            (Self::TwoLeave(s_l1, s_l2), Self::TwoLeave(o_l1, o_l2)) => {
+                s_l1 == o_l1 && s_l2 == o_l2
+            }
+            (Self::ThreeLeave(s_l1, s_l2, s_l3), Self::ThreeLeave(o_l1, o_l2, o_l3)) => {
+                s_l1 == o_l1 && s_l2 == o_l2 && s_l3 == o_l3
+            }
             _ => false,
         }
     }
 }
 
-impl std::cmp::PartialOrd for Frame {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.score().partial_cmp(&other.score())
+impl std::cmp::PartialOrd for Frame {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.score().partial_cmp(&other.score())
+    }
+}
+
+impl std::fmt::Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbols(Variant::TenPin).concat())
+    }
+}
+
+impl std::str::FromStr for Frame {
+    type Err = ParseScoreError;
+
+    // Parses a single frame, falling back to the 10th frame rules when there are extra balls
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let symbols: Vec<(usize, char)> = s
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .collect();
+        let end = s.chars().count();
+
+        match parse_frame(&symbols, 1, end) {
+            Ok((frame, used)) if used == symbols.len() => Ok(frame),
+            Ok(_) => match parse_frame(&symbols, 10, end)? {
+                (frame, used) if used == symbols.len() => Ok(frame),
+                (_, used) => Err(ParseScoreError::build(
+                    symbols[used].0,
+                    10,
+                    ParseScoreErrorKind::ExtraBall,
+                )),
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// Reads the balls of frame `no` from the front of `symbols`, returning the frame and symbols used
+fn parse_frame(
+    symbols: &[(usize, char)],
+    no: u8,
+    end: usize,
+) -> Result<(Frame, usize), ParseScoreError> {
+    let mut balls: Vec<u8> = Vec::new();
+    let mut standing: u8 = 10;
+    let mut fresh = true;
+
+    for (pos, c) in symbols.iter() {
+        let error = |kind| ParseScoreError::build(*pos, no, kind);
+
+        let ball = match c {
+            'X' | 'x' if fresh => 10,
+            'X' | 'x' => return Err(error(ParseScoreErrorKind::StrikeNotFirst)),
+            '/' if !fresh => standing,
+            '/' => return Err(error(ParseScoreErrorKind::SpareFirst)),
+            '-' | 'F' | 'f' | 'G' | 'g' => 0,
+            '0'..='9' => match *c as u8 - b'0' {
+                d if d == standing => return Err(error(ParseScoreErrorKind::ExpectedSpare)),
+                d if d > standing => return Err(error(ParseScoreErrorKind::TooManyPins)),
+                d => d,
+            },
+            _ => return Err(error(ParseScoreErrorKind::InvalidSymbol(*c))),
+        };
+
+        balls.push(ball);
+        standing -= ball;
+
+        let done = if no != 10 {
+            standing == 0 || balls.len() == 2
+        } else {
+            balls.len() == 3 || (balls.len() == 2 && balls[0] + balls[1] < 10)
+        };
+
+        if done {
+            let used = balls.len();
+
+            return Ok((Frame::from(balls), used));
+        }
+
+        // Only the 10th frame resets the rack
+        fresh = standing == 0;
+
+        if fresh {
+            standing = 10;
+        }
+    }
+
+    let position = symbols.last().map_or(end, |(pos, _)| pos + 1);
+
+    Err(ParseScoreError::build(
+        position,
+        no,
+        ParseScoreErrorKind::IncompleteFrame,
+    ))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseScoreErrorKind {
+    InvalidSymbol(char),
+    SpareFirst,
+    StrikeNotFirst,
+    ExpectedSpare,
+    TooManyPins,
+    IncompleteFrame,
+    ExtraBall,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseScoreError {
+    position: usize,
+    frame: u8,
+    kind: ParseScoreErrorKind,
+}
+
+impl ParseScoreError {
+    // Constructor
+    fn build(position: usize, frame: u8, kind: ParseScoreErrorKind) -> Self {
+        Self {
+            position,
+            frame,
+            kind,
+        }
+    }
+
+    // Getter
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn frame(&self) -> u8 {
+        self.frame
+    }
+
+    pub fn kind(&self) -> ParseScoreErrorKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for ParseScoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ParseScoreErrorKind::InvalidSymbol(c) => write!(f, "Invalid symbol '{}'", c)?,
+            ParseScoreErrorKind::SpareFirst => write!(f, "Spare on the first ball")?,
+            ParseScoreErrorKind::StrikeNotFirst => write!(f, "Strike after the first ball")?,
+            ParseScoreErrorKind::ExpectedSpare => write!(f, "Pinfall should be marked as '/'")?,
+            ParseScoreErrorKind::TooManyPins => write!(f, "More pins than are standing")?,
+            ParseScoreErrorKind::IncompleteFrame => write!(f, "Incomplete frame")?,
+            ParseScoreErrorKind::ExtraBall => write!(f, "Extra ball after the 10th frame")?,
+        };
+
+        write!(f, " at position {} (frame {})", self.position, self.frame)
+    }
+}
+
+impl std::error::Error for ParseScoreError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Marker {
+    Foul,
+    Gutter,
+    Split,
+    Pocket,
+}
+
+// Marker on ball `ball` of frame `frame`, both numbered from 1
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BallMarker {
+    frame: u8,
+    ball: u8,
+    marker: Marker,
+}
+
+impl BallMarker {
+    // Constructor
+    pub fn build(frame: u8, ball: u8, marker: Marker) -> Self {
+        Self {
+            frame,
+            ball,
+            marker,
+        }
+    }
+
+    // Getter
+    pub fn frame(&self) -> u8 {
+        self.frame
+    }
+
+    pub fn ball(&self) -> u8 {
+        self.ball
+    }
+
+    pub fn marker(&self) -> Marker {
+        self.marker
+    }
+}
+
+// Pins that deadwood knocked down on ball `ball` of frame `frame`, both numbered from 1. They are
+// part of the count of that ball
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deadwood {
+    frame: u8,
+    ball: u8,
+    pins: u8,
+}
+
+impl Deadwood {
+    // Constructor
+    pub fn build(frame: u8, ball: u8, pins: u8) -> Self {
+        Self { frame, ball, pins }
+    }
+
+    // Getter
+    pub fn frame(&self) -> u8 {
+        self.frame
+    }
+
+    pub fn ball(&self) -> u8 {
+        self.ball
+    }
+
+    pub fn pins(&self) -> u8 {
+        self.pins
     }
 }
 
@@ -244,17 +1157,34 @@ impl std::cmp::PartialOrd for Frame {
 pub struct Game {
     game_num: u8,
     frames: Box<[Frame]>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    markers: Vec<BallMarker>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deadwood: Vec<Deadwood>,
+    #[serde(default, skip_serializing_if = "Variant::is_ten_pin")]
+    variant: Variant,
+    // Pins on the first ball that count as a strike
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    no_tap: Option<u8>,
 }
 
 impl Game {
     // Constructor
     pub fn build(num: u8) -> Self {
+        Self::build_with_variant(num, Variant::TenPin)
+    }
+
+    pub fn build_with_variant(num: u8, variant: Variant) -> Self {
         Self {
             game_num: num,
             frames: (1..=10)
                 .map(|_| Frame::Uninit)
                 .collect::<Vec<Frame>>()
                 .into_boxed_slice(),
+            markers: Vec::new(),
+            deadwood: Vec::new(),
+            variant,
+            no_tap: None,
         }
     }
 
@@ -266,7 +1196,32 @@ impl Game {
         Self {
             game_num: num,
             frames: frames.into_boxed_slice(),
+            markers: Vec::new(),
+            deadwood: Vec::new(),
+            variant: Variant::TenPin,
+            no_tap: None,
+        }
+    }
+
+    pub fn build_from_rolls(num: u8, rolls: &[Roll]) -> Result<Self, RollError> {
+        Self::build_from_rolls_with(num, Variant::TenPin, rolls)
+    }
+
+    pub fn build_from_rolls_with(
+        num: u8,
+        variant: Variant,
+        rolls: &[Roll],
+    ) -> Result<Self, RollError> {
+        let mut scorer = Scorer::build_with_variant(variant, Traditional);
+
+        for roll in rolls {
+            scorer.add_roll(*roll)?;
         }
+
+        Ok(Self {
+            frames: scorer.frames().into_boxed_slice(),
+            ..Self::build_with_variant(num, variant)
+        })
     }
 
     // Getter
@@ -278,6 +1233,49 @@ impl Game {
         &self.frames
     }
 
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn no_tap(&self) -> Option<u8> {
+        self.no_tap
+    }
+
+    pub fn is_no_tap(&self) -> bool {
+        self.no_tap.is_some()
+    }
+
+    pub fn scorecard(&self) -> Scorecard<'_> {
+        self.scorecard_with(Traditional)
+    }
+
+    pub fn scorecard_with<R: ScoringRules>(&self, rules: R) -> Scorecard<'_, R> {
+        Scorecard { game: self, rules }
+    }
+
+    pub fn markers(&self) -> &[BallMarker] {
+        &self.markers
+    }
+
+    pub fn marker(&self, frame_no: u8, ball: u8) -> Option<Marker> {
+        self.markers
+            .iter()
+            .find(|m| m.frame == frame_no && m.ball == ball)
+            .map(|m| m.marker)
+    }
+
+    pub fn deadwood(&self) -> &[Deadwood] {
+        &self.deadwood
+    }
+
+    // Pins of the ball that deadwood knocked down
+    pub fn deadwood_on(&self, frame_no: u8, ball: u8) -> u8 {
+        self.deadwood
+            .iter()
+            .find(|d| d.frame == frame_no && d.ball == ball)
+            .map_or(0, |d| d.pins)
+    }
+
     pub fn game_num_mut(&mut self) -> &mut u8 {
         &mut self.game_num
     }
@@ -286,207 +1284,262 @@ impl Game {
         &mut self.frames
     }
 
+    // Setter
+    pub fn set_no_tap(&mut self, threshold: Option<u8>) {
+        self.no_tap = threshold;
+    }
+
+    // Each ball carries at most one marker, `None` clears it
+    pub fn set_marker(&mut self, frame_no: u8, ball: u8, marker: Option<Marker>) {
+        self.markers
+            .retain(|m| !(m.frame == frame_no && m.ball == ball));
+
+        if let Some(marker) = marker {
+            self.markers.push(BallMarker::build(frame_no, ball, marker));
+            self.markers.sort_by_key(|m| (m.frame, m.ball));
+        }
+    }
+
+    // 0 clears it
+    pub fn set_deadwood(&mut self, frame_no: u8, ball: u8, pins: u8) {
+        self.deadwood
+            .retain(|d| !(d.frame == frame_no && d.ball == ball));
+
+        if pins > 0 {
+            self.deadwood.push(Deadwood::build(frame_no, ball, pins));
+            self.deadwood.sort_by_key(|d| (d.frame, d.ball));
+        }
+    }
+
     // Method
     pub fn is_valid(&self) -> bool {
-        (1..).zip(self.frames.iter()).all(|(n, f)| f.is_valid_no(n))
+        self.is_valid_no_tap()
+            && (1..)
+                .zip(self.effective_frames().iter())
+                .all(|(n, f)| f.is_valid_no_with(self.variant, n))
+            && self.markers.iter().all(|m| self.is_valid_marker(m))
+            && self.deadwood.iter().all(|d| self.is_valid_deadwood(d))
     }
 
-    // Statistics
-    pub fn score(&self) -> u16 {
-        let mut score: u16 = 0;
+    // Every frame has to be played out the way the rules allow
+    pub fn is_valid_with<R: ScoringRules>(&self, rules: R) -> bool {
+        let mut scorer = Scorer::build_with_variant(self.variant, rules);
+        let bowled = scorer.add_frames(&self.effective_frames()).is_ok();
 
-        let iterator = izip!(
-            self.frames.iter(),
-            self.frames.iter().skip(1),
-            self.frames.iter().skip(2)
-        );
+        self.is_valid_no_tap()
+            && bowled
+            && scorer.is_complete()
+            && scorer.score() <= self.variant.max_score()
+            && self
+                .effective_frames()
+                .iter()
+                .all(|f| f.is_valid_with(self.variant))
+            && self.markers.iter().all(|m| self.is_valid_marker(m))
+            && self.deadwood.iter().all(|d| self.is_valid_deadwood(d))
+    }
 
-        for (f1, f2, f3) in iterator {
-            if f1.is_strike() {
-                score += f1.score() as u16;
+    fn is_valid_no_tap(&self) -> bool {
+        self.no_tap
+            .is_none_or(|t| (1..=self.variant.rack_value()).contains(&t))
+    }
 
-                if f2.is_strike() {
-                    score += f2.score() as u16;
+    // Frames as scored, no-tap strikes count as a full rack
+    pub(crate) fn effective_frames(&self) -> Vec<Frame> {
+        match self.no_tap {
+            Some(threshold) => self
+                .frames
+                .iter()
+                .map(|f| f.with_no_tap(self.variant, threshold))
+                .collect(),
+            None => self.frames.to_vec(),
+        }
+    }
 
-                    score += match f3 {
-                        Frame::Uninit => 0,
-                        Frame::TwoFrame(t1, _) => *t1,
-                        Frame::ThreeFrame(t1, _, _) => *t1,
-                    } as u16;
-                } else {
-                    score += f2.score() as u16;
-                }
-            } else if f1.is_spare() {
-                score += f1.score() as u16;
+    // Fouls and gutters score no pins, splits and pocket hits are only on a full rack
+    fn is_valid_marker(&self, marker: &BallMarker) -> bool {
+        if !(1..=10).contains(&marker.frame) || marker.ball == 0 {
+            return false;
+        }
 
-                if let Frame::TwoFrame(t1, _) = f2 {
-                    score += *t1 as u16;
-                }
-            } else {
-                score += f1.score() as u16;
-            }
+        let balls = self.effective_frames()[(marker.frame - 1) as usize].balls_with(self.variant);
+        let idx = (marker.ball - 1) as usize;
+        let rack = self.variant.rack_value();
+
+        if idx >= balls.len() {
+            return false;
         }
 
-        let frame_9 = &self.frames[8];
-        let frame_10 = &self.frames[9];
+        let full_rack = balls[..idx].iter().sum::<u8>() % rack == 0;
+
+        match marker.marker {
+            Marker::Foul | Marker::Gutter => balls[idx] == 0,
+            Marker::Split => full_rack && balls[idx] != rack,
+            Marker::Pocket => full_rack,
+        }
+    }
 
-        if frame_9.is_strike() {
-            score += 10;
+    // Only lying on the lane after an earlier ball at the same rack, and part of the ball's count
+    fn is_valid_deadwood(&self, deadwood: &Deadwood) -> bool {
+        if !self.variant.has_deadwood() || !(1..=10).contains(&deadwood.frame) || deadwood.ball == 0
+        {
+            return false;
+        }
 
-            score += match frame_10 {
-                Frame::Uninit => 0,
-                Frame::TwoFrame(t1, t2) => t1 + t2,
-                Frame::ThreeFrame(t1, t2, _) => t1 + t2,
-            } as u16;
-        } else if frame_9.is_spare() {
-            score += 10;
+        let balls = self.effective_frames()[(deadwood.frame - 1) as usize].balls_with(self.variant);
+        let idx = (deadwood.ball - 1) as usize;
 
-            score += match frame_10 {
-                Frame::Uninit => 0,
-                Frame::TwoFrame(t1, _) => *t1,
-                Frame::ThreeFrame(t1, _, _) => *t1,
-            } as u16;
-        } else {
-            score += frame_9.score() as u16;
+        if idx >= balls.len() {
+            return false;
         }
 
-        score += frame_10.score() as u16;
+        let full_rack = balls[..idx].iter().sum::<u8>() % self.variant.rack_value() == 0;
 
-        score
+        !full_rack && deadwood.pins <= balls[idx]
     }
 
-    pub fn score_n(&self, frame_no: u8) -> Option<u16> {
-        let iterator = izip!(
-            (1..=10),
-            self.frames.iter(),
-            self.frames.iter().skip(1),
-            self.frames.iter().skip(2)
-        );
+    // Notation of frame `frame_no` with fouls and gutters marked
+    fn frame_notation(&self, frame_no: u8) -> Vec<String> {
+        let mut symbols = self.effective_frames()[(frame_no - 1) as usize].symbols(self.variant);
 
-        let mut score: u16 = 0;
+        for m in self.markers.iter().filter(|m| m.frame == frame_no) {
+            let symbol = match m.marker {
+                Marker::Foul => "F",
+                Marker::Gutter => "G",
+                _ => continue,
+            };
 
-        for (n, f1, f2, f3) in iterator {
-            if n > frame_no {
-                return Some(score);
+            if let Some(c) = symbols.get_mut((m.ball - 1) as usize) {
+                if c == "-" {
+                    *c = symbol.to_string();
+                }
             }
+        }
 
-            if !f1.is_valid() {
-                return None;
-            }
+        symbols
+    }
 
-            if f1.is_strike() {
-                if !f2.is_valid() {
-                    return None;
-                }
+    pub fn rolls(&self) -> Vec<Roll> {
+        self.scorer().rolls().to_vec()
+    }
 
-                if f2.is_strike() {
-                    if !f3.is_valid() {
-                        return None;
-                    }
-
-                    score += f1.score() as u16
-                        + f2.score() as u16
-                        + match f3 {
-                            Frame::Uninit => unreachable!(),
-                            Frame::TwoFrame(t1, _) => *t1,
-                            Frame::ThreeFrame(t1, _, _) => *t1,
-                        } as u16;
-                } else {
-                    score += f1.score() as u16 + f2.score() as u16;
-                }
-            } else if f1.is_spare() {
-                if !f2.is_valid() {
-                    return None;
-                }
+    pub fn scorer(&self) -> Scorer {
+        self.scorer_with(Traditional)
+    }
 
-                if let Frame::TwoFrame(t1, _) = f2 {
-                    score += f1.score() as u16 + *t1 as u16;
-                }
-            } else {
-                score += f1.score() as u16;
-            }
-        }
+    // Frames up to the first one that can not be bowled, `try_score_with` reports that frame
+    pub fn scorer_with<R: ScoringRules>(&self, rules: R) -> Scorer<R> {
+        let mut scorer = Scorer::build_with_variant(self.variant, rules);
+        let _ = scorer.add_frames(&self.effective_frames());
 
-        if frame_no == 8 {
-            return Some(score);
-        }
+        scorer
+    }
 
-        let frame_9 = &self.frames[8];
-        let frame_10 = &self.frames[9];
+    // Statistics
+    // Points of the frames up to the first one that can not be bowled
+    pub fn score(&self) -> u16 {
+        self.score_with(Traditional)
+    }
 
-        if !frame_9.is_valid() {
-            return None;
-        }
+    pub fn score_with<R: ScoringRules>(&self, rules: R) -> u16 {
+        self.scorer_with(rules).score()
+    }
 
-        if frame_9.is_strike() {
-            if !frame_10.is_valid() {
-                return None;
-            }
+    // Points of every played frame, or the first ball of a frame that can not be bowled
+    pub fn try_score(&self) -> Result<u16, RollError> {
+        self.try_score_with(Traditional)
+    }
 
-            score += match frame_10 {
-                Frame::Uninit => return None,
-                Frame::TwoFrame(t1, t2) => 10 + t1 + t2,
-                Frame::ThreeFrame(t1, t2, _) => 10 + t1 + t2,
-            } as u16;
-        } else if frame_9.is_spare() {
-            if !frame_10.is_valid() {
-                return None;
-            }
+    pub fn try_score_with<R: ScoringRules>(&self, rules: R) -> Result<u16, RollError> {
+        let mut scorer = Scorer::build_with_variant(self.variant, rules);
+        scorer.add_frames(&self.effective_frames())?;
 
-            score += match frame_10 {
-                Frame::Uninit => 0,
-                Frame::TwoFrame(t1, _) => 10 + t1,
-                Frame::ThreeFrame(t1, _, _) => 10 + t1,
-            } as u16;
-        } else {
-            score += frame_9.score() as u16;
-        }
+        Ok(scorer.score())
+    }
 
-        if frame_no == 9 {
-            return Some(score);
-        }
+    pub fn score_n(&self, frame_no: u8) -> Option<u16> {
+        self.score_n_with(Traditional, frame_no)
+    }
 
-        if !frame_10.is_valid() {
-            return None;
+    pub fn score_n_with<R: ScoringRules>(&self, rules: R, frame_no: u8) -> Option<u16> {
+        self.scorer_with(rules).score_n(frame_no)
+    }
+
+    // Highest score still reachable if every remaining frame is a strike
+    pub fn possible_score(&self) -> u16 {
+        self.possible_score_with(Traditional)
+    }
+
+    pub fn possible_score_with<R: ScoringRules>(&self, rules: R) -> u16 {
+        let mut game = self.clone();
+        let rack = self.variant.rack_value();
+
+        for (n, f) in (1..).zip(game.frames.iter_mut()) {
+            if matches!(f, Frame::Uninit) {
+                *f = if n == 10 && rules.fill_balls() {
+                    Frame::ThreeFrame(rack, rack, rack)
+                } else {
+                    Frame::TwoFrame(rack, 0)
+                };
+            }
         }
 
-        Some(score + frame_10.score() as u16)
+        game.score_with(rules)
+    }
+
+    // Lowest score reachable if every remaining frame is a gutter
+    pub fn guaranteed_score(&self) -> u16 {
+        self.guaranteed_score_with(Traditional)
     }
 
-    pub fn possible_score() -> u16 {
-        todo!() // Get possible score
+    pub fn guaranteed_score_with<R: ScoringRules>(&self, rules: R) -> u16 {
+        let mut game = self.clone();
+
+        let gutters = match self.variant.balls() {
+            2 => Frame::TwoFrame(0, 0),
+            _ => Frame::ThreeFrame(0, 0, 0),
+        };
+
+        for f in game.frames.iter_mut() {
+            if matches!(f, Frame::Uninit) {
+                *f = gutters.clone();
+            }
+        }
+
+        game.score_with(rules)
     }
 
     pub fn pin_count(&self) -> u8 {
-        self.frames.iter().fold(0, |acc, f| match f {
-            Frame::Uninit => acc,
-            Frame::TwoFrame(t1, t2) => acc + t1 + t2,
-            Frame::ThreeFrame(t1, t2, t3) => acc + t1 + t2 + t3,
-        })
+        self.frames.iter().fold(0, |acc, f| acc + f.score())
     }
 
     pub fn num_strikes(&self) -> u8 {
-        self.frames.iter().fold(0, |acc, f| acc + f.num_strikes())
+        self.effective_frames()
+            .iter()
+            .fold(0, |acc, f| acc + f.num_strikes_with(self.variant))
     }
 
     pub fn num_spares(&self) -> u8 {
-        self.frames.iter().fold(0, |acc, f| acc + f.num_spares())
+        self.effective_frames()
+            .iter()
+            .fold(0, |acc, f| acc + f.num_spares_with(self.variant))
     }
 
     pub fn strike_chances(&self) -> u8 {
-        self.frames
+        self.effective_frames()
             .iter()
-            .fold(0, |acc, f| acc + f.strike_chances())
+            .fold(0, |acc, f| acc + f.strike_chances_with(self.variant))
     }
 
     pub fn spare_chances(&self) -> u8 {
-        self.frames.iter().fold(0, |acc, f| acc + f.spare_chances())
+        self.effective_frames()
+            .iter()
+            .fold(0, |acc, f| acc + f.spare_chances_with(self.variant))
     }
 
     pub fn open_frames(&self) -> u8 {
-        self.frames
+        self.effective_frames()
             .iter()
-            .filter(|f| !f.is_strike() && !f.is_spare())
+            .filter(|f| !f.is_strike_with(self.variant) && !f.is_spare_with(self.variant))
             .count() as u8
     }
 
@@ -494,14 +1547,51 @@ impl Game {
         10 - self.open_frames()
     }
 
-    pub fn avg_first_ball_pinfall(&self) -> f32 {
-        let total_first_ball_pins = self.frames.iter().fold(0, |acc, f| match f {
-            Frame::Uninit => acc,
+    pub fn num_splits(&self) -> u8 {
+        self.frames.iter().fold(0, |acc, f| acc + f.num_splits())
+    }
+
+    pub fn num_converted_splits(&self) -> u8 {
+        self.frames
+            .iter()
+            .fold(0, |acc, f| acc + f.num_converted_splits())
+    }
+
+    // Racks with a recorded leave whose first ball was not a strike
+    pub fn split_chances(&self) -> u8 {
+        self.frames.iter().fold(0, |acc, f| acc + f.split_chances())
+    }
+
+    pub fn first_ball_pins(&self) -> u8 {
+        self.frames.iter().fold(0, |acc, f| match f.counts() {
             Frame::TwoFrame(t1, _) => acc + t1,
             Frame::ThreeFrame(t1, _, _) => acc + t1,
-        });
+            _ => acc,
+        })
+    }
+
+    pub fn avg_first_ball_pinfall(&self) -> f32 {
+        self.first_ball_pins() as f32 / 10.0
+    }
+
+    pub fn num_markers(&self, marker: Marker) -> u8 {
+        self.markers.iter().filter(|m| m.marker == marker).count() as u8
+    }
+
+    pub fn num_fouls(&self) -> u8 {
+        self.num_markers(Marker::Foul)
+    }
+
+    pub fn num_gutters(&self) -> u8 {
+        self.num_markers(Marker::Gutter)
+    }
+
+    pub fn num_pocket_hits(&self) -> u8 {
+        self.num_markers(Marker::Pocket)
+    }
 
-        total_first_ball_pins as f32 / 10.0
+    pub fn deadwood_pins(&self) -> u8 {
+        self.deadwood.iter().fold(0, |acc, d| acc + d.pins)
     }
 }
 
@@ -513,6 +1603,10 @@ impl std::cmp::PartialEq for Game {
                 .iter()
                 .zip(other.frames.iter())
                 .all(|(g1, g2)| g1 == g2)
+            && self.markers == other.markers
+            && self.deadwood == other.deadwood
+            && self.variant == other.variant
+            && self.no_tap == other.no_tap
     }
 }
 
@@ -524,95 +1618,109 @@ impl std::cmp::PartialOrd for Game {
 
 impl std::fmt::Display for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let cols = match self.frames()[9] {
-            Frame::ThreeFrame(..) => 83,
-            _ => 81,
-        };
-        let top_border: String = (0..cols).map(|_| "=").collect();
-        let mid_border: String = (1..=10)
-            .map(|n| {
-                if n == 10 && cols == 83 {
-                    "      ---|"
-                } else {
-                    "    ---|"
+        let frames = (1..=10)
+            .zip(self.frames.iter())
+            .take_while(|(_, fr)| !matches!(fr, Frame::Uninit))
+            .map(|(n, _)| self.frame_notation(n).concat())
+            .join(" ");
+
+        write!(f, "{}", frames)
+    }
+}
+
+impl std::str::FromStr for Game {
+    type Err = ParseScoreError;
+
+    // Parsed games are numbered 1, frames not in `s` are left uninitialized
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let symbols: Vec<(usize, char)> = s
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .collect();
+        let end = s.chars().count();
+
+        let mut game = Game::build(1);
+        let mut markers = Vec::new();
+        let mut used = 0;
+
+        for (no, frame) in (1..).zip(game.frames_mut().iter_mut()) {
+            if used == symbols.len() {
+                break;
+            }
+
+            let (parsed, n) = parse_frame(&symbols[used..], no, end)?;
+
+            *frame = parsed;
+
+            for (ball, (_, c)) in (1..).zip(symbols[used..used + n].iter()) {
+                match c {
+                    'F' | 'f' => markers.push(BallMarker::build(no, ball, Marker::Foul)),
+                    'G' | 'g' => markers.push(BallMarker::build(no, ball, Marker::Gutter)),
+                    _ => (),
                 }
+            }
+
+            used += n;
+        }
+
+        if used != symbols.len() {
+            return Err(ParseScoreError::build(
+                symbols[used].0,
+                10,
+                ParseScoreErrorKind::ExtraBall,
+            ));
+        }
+
+        game.markers = markers;
+
+        Ok(game)
+    }
+}
+
+// Boxed scorecard with the running score under each frame
+pub struct Scorecard<'a, R: ScoringRules = Traditional> {
+    game: &'a Game,
+    rules: R,
+}
+
+impl<R: ScoringRules> std::fmt::Display for Scorecard<'_, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cells: Vec<String> = (1..=10)
+            .map(|n| match self.game.frame_notation(n).as_slice() {
+                [] => "  |  ".to_string(),
+                [b1] if b1 == "X" => "  | X".to_string(),
+                [b1] => format!("{} |  ", b1),
+                [b1, b2] => format!("{} | {}", b1, b2),
+                [b1, b2, b3, ..] => format!("{} {} | {}", b1, b2, b3),
             })
             .collect();
+        // Frames with a third ball or multi-digit counts take wider cells
+        let widths: Vec<usize> = cells.iter().map(|c| c.chars().count()).collect();
 
-        let frame_scores = self
-            .frames()
+        let cols = widths.iter().map(|w| w + 3).sum::<usize>() + 1;
+        let top_border = "=".repeat(cols);
+        let mid_border: String = widths
             .iter()
-            .map(|fr| match fr {
-                Frame::TwoFrame(10, 0) => "  | X".to_string(),
-                Frame::TwoFrame(t1, t2) if t1 + t2 == 10 => {
-                    if *t1 == 0 {
-                        "- | /".to_string()
-                    } else {
-                        format!("{} | /", t1)
-                    }
-                }
-                Frame::TwoFrame(t1, t2) if t1 + t2 <= 10 => {
-                    if *t1 == 0 && *t2 == 0 {
-                        "- | -".to_string()
-                    } else if *t1 == 0 {
-                        format!("- | {}", t2)
-                    } else if *t2 == 0 {
-                        format!("{} | -", t1)
-                    } else {
-                        format!("{} | {}", t1, t2)
-                    }
-                }
-                Frame::ThreeFrame(10, 10, 10) => "X X | X".to_string(),
-                Frame::ThreeFrame(10, 10, t3) => {
-                    if *t3 == 0 {
-                        "X X | -".to_string()
-                    } else {
-                        format!("X X | {}", t3)
-                    }
-                }
-                Frame::ThreeFrame(10, t2, t3) if t2 + t3 == 10 => {
-                    if *t2 == 0 {
-                        "X - | /".to_string()
-                    } else {
-                        format!("X {} | /", t2)
-                    }
-                }
-                Frame::ThreeFrame(t1, t2, 10) if t1 + t2 == 10 => {
-                    if *t1 == 0 {
-                        "- / | X".to_string()
-                    } else {
-                        format!("{} / | X", t1)
-                    }
-                }
-                Frame::ThreeFrame(t1, t2, t3) if t1 + t2 == 10 => {
-                    if *t1 == 0 && *t3 == 0 {
-                        "- / | -".to_string()
-                    } else if *t1 == 0 {
-                        format!("- / | {}", t3)
-                    } else if *t3 == 0 {
-                        format!("{} / | -", t1)
-                    } else {
-                        format!("{} / | {}", t1, t3)
-                    }
-                }
-                Frame::Uninit => "  |  ".to_string(),
-                _ => unreachable!(),
-            })
-            .join(" | ");
+            .map(|w| format!("{}---|", " ".repeat(w - 1)))
+            .collect();
+
+        let frame_scores = cells.join(" | ");
 
-        let cumul_scores = (1..=10)
-            .zip(self.frames().iter())
-            .map(|(n, fr)| match fr {
-                Frame::ThreeFrame(..) => format!(
-                    "   {}   ",
-                    self.score_n(n)
-                        .map_or("   ".to_string(), |val| format!("{: >3}", val))
-                ),
-                _ => format!(
-                    "  {}  ",
-                    self.score_n(n)
-                        .map_or("   ".to_string(), |val| format!("{: >3}", val))
-                ),
+        let scores = self.game.scorer_with(self.rules).frame_scores();
+        let cumul_scores = scores
+            .iter()
+            .zip(widths.iter())
+            .map(|(score, w)| {
+                let score = score.map_or("   ".to_string(), |val| format!("{: >3}", val));
+                let pad = w - 1;
+
+                format!(
+                    "{}{}{}",
+                    " ".repeat(pad / 2),
+                    score,
+                    " ".repeat(pad - pad / 2)
+                )
             })
             .join("|");
 
@@ -620,40 +1728,145 @@ impl std::fmt::Display for Game {
             f,
             "{}\n| {} |\n|{}\n|{}|\n{}",
             top_border, frame_scores, mid_border, cumul_scores, top_border
-        )
+        )?;
+
+        // Splits and pocket hits have no notation, list them under the card
+        let notes = [(Marker::Split, "Splits"), (Marker::Pocket, "Pocket")]
+            .iter()
+            .filter_map(|(marker, label)| {
+                let marked = self
+                    .game
+                    .markers()
+                    .iter()
+                    .filter(|m| m.marker() == *marker)
+                    .map(|m| format!("{}.{}", m.frame(), m.ball()))
+                    .join(" ");
+
+                (!marked.is_empty()).then(|| format!("{}: {}", label, marked))
+            })
+            .join(" | ");
+
+        let deadwood = self
+            .game
+            .deadwood()
+            .iter()
+            .map(|d| format!("{}.{} ({})", d.frame(), d.ball(), d.pins()))
+            .join(" ");
+
+        let notes = match deadwood.is_empty() {
+            true => notes,
+            false if notes.is_empty() => format!("Deadwood: {}", deadwood),
+            false => format!("{} | Deadwood: {}", notes, deadwood),
+        };
+
+        if !notes.is_empty() {
+            write!(f, "\n{}", notes)?;
+        }
+
+        Ok(())
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Games {
     #[serde(rename = "_id")]
-    date: Date,
+    key: SessionKey,
+    #[serde(default)]
+    session: Session,
     games: Vec<Game>,
+    #[serde(default)]
+    rules: RuleSet,
+    // No-tap games are left out of the statistics unless asked for
+    #[serde(skip)]
+    include_no_tap: bool,
 }
 
 impl Games {
     // Constructor
     pub fn build() -> Self {
         Games {
-            date: Date::build(),
+            key: SessionKey::build(Bowler::default(), Date::build(), 1),
+            session: Session::build(),
             games: Vec::new(),
+            rules: RuleSet::default(),
+            include_no_tap: false,
         }
     }
 
     pub fn build_with(date: Date, game: Game) -> Self {
         Games {
-            date,
+            key: SessionKey::build(Bowler::default(), date, 1),
+            session: Session::default(),
             games: vec![game],
+            rules: RuleSet::default(),
+            include_no_tap: false,
         }
     }
 
     pub fn build_from_vec(date: Date, games: Vec<Game>) -> Self {
-        Games { date, games }
+        Self::build_for(SessionKey::build(Bowler::default(), date, 1), games)
+    }
+
+    pub fn build_for(key: SessionKey, games: Vec<Game>) -> Self {
+        Games {
+            key,
+            session: Session::default(),
+            games,
+            rules: RuleSet::default(),
+            include_no_tap: false,
+        }
+    }
+
+    // Every game of a day's sessions in order, scored with the rules of the first session
+    pub fn build_from_sessions(sessions: &[Games]) -> Self {
+        let key = sessions.first().map_or_else(
+            || SessionKey::build(Bowler::default(), Date::build(), SessionKey::ALL_SESSIONS),
+            |first| {
+                SessionKey::build(
+                    first.bowler().clone(),
+                    first.date(),
+                    SessionKey::ALL_SESSIONS,
+                )
+            },
+        );
+
+        let games = sessions
+            .iter()
+            .flat_map(|s| s.games.iter())
+            .zip(1..)
+            .map(|(g, n)| {
+                let mut game = g.clone();
+                *game.game_num_mut() = n;
+                game
+            })
+            .collect();
+
+        Games {
+            rules: sessions.first().map_or(RuleSet::default(), |s| s.rules),
+            include_no_tap: sessions.iter().any(|s| s.include_no_tap),
+            ..Self::build_for(key, games)
+        }
     }
 
     // Getter
+    pub fn key(&self) -> &SessionKey {
+        &self.key
+    }
+
+    pub fn bowler(&self) -> &Bowler {
+        self.key.bowler()
+    }
+
     pub fn date(&self) -> Date {
-        self.date
+        self.key.date()
+    }
+
+    pub fn session_id(&self) -> u8 {
+        self.key.session_id()
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
     }
 
     pub fn games(&self) -> &[Game] {
@@ -664,57 +1877,162 @@ impl Games {
         &mut self.games
     }
 
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    pub fn includes_no_tap(&self) -> bool {
+        self.include_no_tap
+    }
+
+    // Setter
+    pub fn set_key(&mut self, key: SessionKey) {
+        self.key = key;
+    }
+
+    pub fn set_session(&mut self, session: Session) {
+        self.session = session;
+    }
+
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
+    pub fn set_include_no_tap(&mut self, include: bool) {
+        self.include_no_tap = include;
+    }
+
     // Method
+    // Kept in game number order, so a game put back from the trash goes back where it was
     pub fn add_game(&mut self, game: Game) {
-        self.games.push(game);
+        let idx = self.insert_position(game.game_num());
+        self.games.insert(idx, game);
+    }
+
+    // Where a game numbered `game_num` goes among the games, after those numbered up to it
+    pub(crate) fn insert_position(&self, game_num: u8) -> usize {
+        self.games
+            .iter()
+            .position(|g| g.game_num() > game_num)
+            .unwrap_or(self.games.len())
+    }
+
+    pub fn remove_game(&mut self, game_num: u8) -> Option<Game> {
+        let idx = self.games.iter().position(|g| g.game_num() == game_num)?;
+
+        Some(self.games.remove(idx))
     }
 
     pub fn is_valid(&self) -> bool {
         self.games().iter().zip(1..).all(|(g, n)| g.game_num() == n)
-            && self.games().iter().all(|f| f.is_valid())
+            && self.games().iter().all(|f| f.is_valid_with(self.rules))
+    }
+
+    // Games the statistics are taken over
+    fn counted_games(&self) -> impl Iterator<Item = &Game> {
+        self.games
+            .iter()
+            .filter(|g| self.include_no_tap || !g.is_no_tap())
     }
 
     pub fn average(&self) -> f32 {
-        let total_score = self.games.iter().fold(0, |acc, x| acc + x.score());
-        let num_games = self.games.len();
+        let total_score = self
+            .counted_games()
+            .fold(0, |acc, x| acc + x.score_with(self.rules));
+        let num_games = self.counted_games().count();
 
         total_score as f32 / num_games as f32
     }
 
     pub fn strike_rate(&self) -> f32 {
-        let total_strikes = self.games.iter().fold(0, |acc, g| acc + g.num_strikes());
-        let num_strike_chances = self.games.iter().fold(0, |acc, f| acc + f.strike_chances());
+        let total_strikes = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.num_strikes() as u32);
+        let num_strike_chances = self
+            .counted_games()
+            .fold(0u32, |acc, f| acc + f.strike_chances() as u32);
 
         total_strikes as f32 / num_strike_chances as f32
     }
 
     pub fn spare_rate(&self) -> f32 {
-        let total_spares = self.games.iter().fold(0, |acc, g| acc + g.num_spares());
-        let num_spare_chances = self.games.iter().fold(0, |acc, f| acc + f.spare_chances());
+        let total_spares = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.num_spares() as u32);
+        let num_spare_chances = self
+            .counted_games()
+            .fold(0u32, |acc, f| acc + f.spare_chances() as u32);
 
         total_spares as f32 / num_spare_chances as f32
     }
 
+    pub fn split_rate(&self) -> f32 {
+        let total_splits = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.num_splits() as u32);
+        let num_split_chances = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.split_chances() as u32);
+
+        total_splits as f32 / num_split_chances as f32
+    }
+
+    pub fn split_conversion_rate(&self) -> f32 {
+        let total_converted = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.num_converted_splits() as u32);
+        let total_splits = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.num_splits() as u32);
+
+        total_converted as f32 / total_splits as f32
+    }
+
     pub fn open_frame_rate(&self) -> f32 {
-        let total_open_frames = self.games.iter().fold(0, |acc, g| acc + g.open_frames());
-        let total_frames = self.games.len() * 10;
+        let total_open_frames = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.open_frames() as u32);
+        let total_frames = self.counted_games().count() * 10;
 
         total_open_frames as f32 / total_frames as f32
     }
 
     pub fn clean_frame_rate(&self) -> f32 {
-        let total_clean_frames = self.games.iter().fold(0, |acc, g| acc + g.clean_frames());
-        let total_frames = self.games.len() * 10;
+        let total_clean_frames = self
+            .counted_games()
+            .fold(0u32, |acc, g| acc + g.clean_frames() as u32);
+        let total_frames = self.counted_games().count() * 10;
 
         total_clean_frames as f32 / total_frames as f32
     }
 
+    pub fn marker_count(&self, marker: Marker) -> u16 {
+        self.counted_games()
+            .fold(0, |acc, g| acc + g.num_markers(marker) as u16)
+    }
+
+    pub fn foul_count(&self) -> u16 {
+        self.marker_count(Marker::Foul)
+    }
+
+    pub fn gutter_count(&self) -> u16 {
+        self.marker_count(Marker::Gutter)
+    }
+
+    pub fn pocket_count(&self) -> u16 {
+        self.marker_count(Marker::Pocket)
+    }
+
+    pub fn deadwood_pins(&self) -> u16 {
+        self.counted_games()
+            .fold(0, |acc, g| acc + g.deadwood_pins() as u16)
+    }
+
     pub fn avg_first_ball_pinfall(&self) -> f32 {
         let total_average = self
-            .games
-            .iter()
+            .counted_games()
             .fold(0.0, |acc: f32, g: &Game| acc + g.avg_first_ball_pinfall());
-        let total_games = self.games.len();
+        let total_games = self.counted_games().count();
 
         total_average / total_games as f32
     }
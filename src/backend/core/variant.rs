@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use super::scoring::Roll;
+
+// Kind of bowling a game was bowled in, ten-pin unless stated otherwise
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Variant {
+    #[default]
+    TenPin,
+    Duckpin,
+    // Fallen pins are left on the lane between balls, the pins that deadwood knocks down count
+    // towards the ball thrown at the rack next
+    Candlepin,
+    // Canadian five-pin, pins are worth 2-3-5-3-2
+    FivePin,
+}
+
+impl Variant {
+    // Getter
+    // Value of each pin numbered from 1
+    pub fn pin_values(&self) -> &'static [u8] {
+        match self {
+            Self::TenPin | Self::Duckpin | Self::Candlepin => &[1; 10],
+            Self::FivePin => &[2, 3, 5, 3, 2],
+        }
+    }
+
+    // Count of a full rack
+    pub fn rack_value(&self) -> u8 {
+        self.pin_values().iter().sum()
+    }
+
+    // Balls per frame before the 10th
+    pub fn balls(&self) -> u8 {
+        match self {
+            Self::TenPin => 2,
+            Self::Duckpin | Self::Candlepin | Self::FivePin => 3,
+        }
+    }
+
+    // Highest score a game can have under any rule set
+    pub fn max_score(&self) -> u16 {
+        self.rack_value() as u16 * 30
+    }
+
+    pub fn is_ten_pin(&self) -> bool {
+        matches!(self, Self::TenPin)
+    }
+
+    // Whether fallen pins stay on the lane for the balls after them
+    pub fn has_deadwood(&self) -> bool {
+        matches!(self, Self::Candlepin)
+    }
+
+    // Method
+    // Whether balls knocking down `counts` one after another can all be taken from a single rack
+    pub fn fits_rack(&self, counts: &[u8]) -> bool {
+        let values = self.pin_values();
+
+        // Any count up to what is standing can be knocked down when every pin counts one
+        if values.iter().all(|v| *v == 1) {
+            return counts.iter().map(|c| *c as usize).sum::<usize>() <= values.len();
+        }
+
+        let value = |pins: u16| -> u8 {
+            (0..values.len())
+                .filter(|i| pins & (1 << i) != 0)
+                .map(|i| values[i])
+                .sum()
+        };
+
+        // Every set of pins that could still be standing, as bit masks
+        let mut standing = vec![(1u16 << values.len()) - 1];
+
+        for count in counts {
+            let mut next = Vec::new();
+
+            for rack in standing {
+                let mut knocked = rack;
+
+                loop {
+                    if value(knocked) == *count && !next.contains(&(rack & !knocked)) {
+                        next.push(rack & !knocked);
+                    }
+
+                    if knocked == 0 {
+                        break;
+                    }
+
+                    knocked = (knocked - 1) & rack;
+                }
+            }
+
+            if next.is_empty() {
+                return false;
+            }
+
+            standing = next;
+        }
+
+        true
+    }
+
+    // Balls after a frame that count towards its score, a strike takes two and a spare one
+    pub fn bonus_balls(&self, frame: &[Roll]) -> usize {
+        let count: u8 = frame.iter().map(Roll::pins).sum();
+
+        match frame.len() {
+            1 if count == self.rack_value() => 2,
+            2 if count == self.rack_value() => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TenPin => write!(f, "Ten-pin"),
+            Self::Duckpin => write!(f, "Duckpin"),
+            Self::Candlepin => write!(f, "Candlepin"),
+            Self::FivePin => write!(f, "Five-pin"),
+        }
+    }
+}
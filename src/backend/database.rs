@@ -1,4 +1,15 @@
+pub mod async_store;
+pub mod db_conn;
+pub mod file_conn;
+pub mod history;
+pub mod integrity;
+pub mod memory_conn;
 pub mod mongodb_conn;
+pub mod query;
+pub mod sqlite_conn;
+pub mod stats;
+pub mod sync;
+pub mod trash;
 
 #[cfg(test)]
 pub mod tests;
@@ -0,0 +1,364 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+};
+
+use tokio::{
+    runtime::Handle,
+    sync::{
+        mpsc,
+        oneshot::{self, error::TryRecvError},
+    },
+};
+
+use super::db_conn::GameStore;
+use super::integrity::IntegrityReport;
+use super::query::{GameQuery, GamesIter};
+use super::stats::GameTotals;
+use super::trash::TrashItem;
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+type Job = Box<dyn FnOnce(&dyn GameStore) + Send>;
+
+// Most sessions a query hands back at once, whatever the limit of the query
+pub const QUERY_PAGE_SIZE: usize = 100;
+
+// Result of a job on the store that may still be running, await it or check it with `try_take`
+pub struct Pending<T> {
+    reply: oneshot::Receiver<Result<T, Error>>,
+}
+
+impl<T> Pending<T> {
+    // Method
+    // `None` while the job is still running, for loops that can not wait such as drawing the TUI
+    pub fn try_take(&mut self) -> Option<Result<T, Error>> {
+        match self.reply.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err(Error::StoreStoppedError)),
+        }
+    }
+
+    // Blocks until the job is done, from async code this is an error as blocking there would panic
+    pub fn wait(self) -> Result<T, Error> {
+        if Handle::try_current().is_ok() {
+            return Err(Error::BlockingInAsyncError);
+        }
+
+        self.reply
+            .blocking_recv()
+            .unwrap_or(Err(Error::StoreStoppedError))
+    }
+}
+
+impl<T> Future for Pending<T> {
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().reply)
+            .poll(cx)
+            .map(|reply| reply.unwrap_or(Err(Error::StoreStoppedError)))
+    }
+}
+
+// Runs a store on a thread of its own so waiting on it never blocks the caller,
+// the blocking `GameStore` calls wait for the same thread
+pub struct AsyncStore {
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl AsyncStore {
+    // Constructor
+    pub fn build(store: Box<dyn GameStore + Send>) -> Self {
+        let (jobs, mut queue) = mpsc::unbounded_channel::<Job>();
+
+        thread::spawn(move || serve(store.as_ref(), &mut queue));
+
+        Self { jobs }
+    }
+
+    // Opens the store on its thread as well, so slow connections do not block the caller either
+    pub fn open<F>(open: F) -> Pending<Self>
+    where
+        F: FnOnce() -> Result<Box<dyn GameStore>, Error> + Send + 'static,
+    {
+        let (reply, pending) = oneshot::channel();
+
+        thread::spawn(move || {
+            let store = match open() {
+                Err(err) => {
+                    let _ = reply.send(Err(err));
+                    return;
+                }
+                Ok(store) => store,
+            };
+
+            let (jobs, mut queue) = mpsc::unbounded_channel::<Job>();
+
+            if reply.send(Ok(Self { jobs })).is_ok() {
+                serve(store.as_ref(), &mut queue);
+            }
+        });
+
+        Pending { reply: pending }
+    }
+
+    // Method
+    // Queues `job`, jobs run one at a time in the order they were queued
+    pub fn run<T, F>(&self, job: F) -> Pending<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn GameStore) -> Result<T, Error> + Send + 'static,
+    {
+        let (reply, pending) = oneshot::channel();
+
+        // When the thread is gone the reply is dropped with the job, which `Pending` reports
+        let _ = self.jobs.send(Box::new(move |store| {
+            let _ = reply.send(job(store));
+        }));
+
+        Pending { reply: pending }
+    }
+
+    pub fn add_game_async(&self, key: &SessionKey, game: &Game) -> Pending<()> {
+        let (key, game) = (key.clone(), game.clone());
+        self.run(move |store| store.add_game(&key, &game))
+    }
+
+    pub fn add_games_async(&self, games: &Games) -> Pending<u8> {
+        let games = games.clone();
+        self.run(move |store| store.add_games(&games))
+    }
+
+    pub fn get_session_async(&self, key: &SessionKey) -> Pending<Option<Games>> {
+        let key = key.clone();
+        self.run(move |store| store.get_session(&key))
+    }
+
+    pub fn get_games_async(&self, bowler: &Bowler, date: Date) -> Pending<Vec<Games>> {
+        let bowler = bowler.clone();
+        self.run(move |store| store.get_games(&bowler, date))
+    }
+
+    // The first `QUERY_PAGE_SIZE` matching sessions at most, move the skip of the query on for
+    // the rest
+    pub fn query_async(&self, query: &GameQuery) -> Pending<Vec<Games>> {
+        let mut query = query.clone();
+        query.set_limit(Some(page_size(query.limit())));
+
+        self.run(move |store| store.query(&query)?.collect())
+    }
+
+    pub fn modify_game_async(&self, key: &SessionKey, game: &Game) -> Pending<()> {
+        let (key, game) = (key.clone(), game.clone());
+        self.run(move |store| store.modify_game(&key, &game))
+    }
+
+    pub fn modify_games_async(&self, games: &Games) -> Pending<()> {
+        let games = games.clone();
+        self.run(move |store| store.modify_games(&games))
+    }
+
+    pub fn remove_game_async(&self, key: &SessionKey, game_num: u8) -> Pending<Option<Game>> {
+        let key = key.clone();
+        self.run(move |store| store.remove_game(&key, game_num))
+    }
+
+    pub fn remove_session_async(&self, key: &SessionKey) -> Pending<Option<Games>> {
+        let key = key.clone();
+        self.run(move |store| store.remove_session(&key))
+    }
+
+    pub fn remove_games_async(&self, bowler: &Bowler, date: Date) -> Pending<Vec<Games>> {
+        let bowler = bowler.clone();
+        self.run(move |store| store.remove_games(&bowler, date))
+    }
+
+    pub fn trash_async(&self) -> Pending<Vec<TrashItem>> {
+        self.run(|store| store.trash())
+    }
+
+    pub fn delete_trashed_async(&self, id: u64) -> Pending<bool> {
+        self.run(move |store| store.delete_trashed(id))
+    }
+
+    pub fn restore_trashed_async(&self, id: u64) -> Pending<Option<SessionKey>> {
+        self.run(move |store| store.restore_trashed(id))
+    }
+
+    pub fn purge_trash_async(&self) -> Pending<u64> {
+        self.run(|store| store.purge_trash())
+    }
+
+    pub fn totals_async(&self, query: &GameQuery, include_no_tap: bool) -> Pending<GameTotals> {
+        let query = query.clone();
+        self.run(move |store| store.totals(&query, include_no_tap))
+    }
+
+    pub fn bowlers_async(&self) -> Pending<Vec<Bowler>> {
+        self.run(|store| store.bowlers())
+    }
+
+    pub fn dates_async(&self, bowler: &Bowler) -> Pending<Vec<Date>> {
+        let bowler = bowler.clone();
+        self.run(move |store| store.dates(&bowler))
+    }
+
+    pub fn drop_all_async(&self) -> Pending<()> {
+        self.run(|store| store.drop_all())
+    }
+
+    pub fn integrity_report_async(&self) -> Pending<IntegrityReport> {
+        self.run(|store| store.integrity_report())
+    }
+
+    pub fn validate_async(&self) -> Pending<bool> {
+        self.run(|store| Ok(store.validate()))
+    }
+}
+
+// Blocking calls for the line based loops, from async code they fail with `BlockingInAsyncError`
+impl GameStore for AsyncStore {
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        self.add_game_async(key, game).wait()
+    }
+
+    fn add_games(&self, games: &Games) -> Result<u8, Error> {
+        self.add_games_async(games).wait()
+    }
+
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        self.get_session_async(key).wait()
+    }
+
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        self.get_games_async(bowler, date).wait()
+    }
+
+    // Fetched a page at a time, each page is a job of its own so other calls can run in between
+    fn query(&self, query: &GameQuery) -> Result<GamesIter<'_>, Error> {
+        Ok(Box::new(Pages {
+            store: self,
+            query: query.clone(),
+            remaining: query.limit(),
+            page: Vec::new().into_iter(),
+            done: false,
+        }))
+    }
+
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        self.modify_game_async(key, game).wait()
+    }
+
+    fn modify_games(&self, games: &Games) -> Result<(), Error> {
+        self.modify_games_async(games).wait()
+    }
+
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
+        self.remove_game_async(key, game_num).wait()
+    }
+
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        self.remove_session_async(key).wait()
+    }
+
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        self.remove_games_async(bowler, date).wait()
+    }
+
+    fn trash(&self) -> Result<Vec<TrashItem>, Error> {
+        self.trash_async().wait()
+    }
+
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error> {
+        self.delete_trashed_async(id).wait()
+    }
+
+    // Restoring and purging run as one job, so nothing else changes the store halfway
+    fn restore_trashed(&self, id: u64) -> Result<Option<SessionKey>, Error> {
+        self.restore_trashed_async(id).wait()
+    }
+
+    fn purge_trash(&self) -> Result<u64, Error> {
+        self.purge_trash_async().wait()
+    }
+
+    fn totals(&self, query: &GameQuery, include_no_tap: bool) -> Result<GameTotals, Error> {
+        self.totals_async(query, include_no_tap).wait()
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error> {
+        self.bowlers_async().wait()
+    }
+
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error> {
+        self.dates_async(bowler).wait()
+    }
+
+    fn drop_all(&self) -> Result<(), Error> {
+        self.drop_all_async().wait()
+    }
+
+    // Checked in one job instead of a page at a time
+    fn integrity_report(&self) -> Result<IntegrityReport, Error> {
+        self.integrity_report_async().wait()
+    }
+}
+
+// Sessions of a query on an `AsyncStore`, only one page of them is held at a time
+struct Pages<'a> {
+    store: &'a AsyncStore,
+    query: GameQuery,
+    // Sessions left before the limit of the query is reached
+    remaining: Option<usize>,
+    page: std::vec::IntoIter<Games>,
+    done: bool,
+}
+
+impl Iterator for Pages<'_> {
+    type Item = Result<Games, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(games) = self.page.next() {
+                return Some(Ok(games));
+            }
+
+            if self.done || self.remaining == Some(0) {
+                return None;
+            }
+
+            let size = page_size(self.remaining);
+            let mut query = self.query.clone();
+            query.set_limit(Some(size));
+
+            match self.store.query_async(&query).wait() {
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                Ok(page) => {
+                    // A short page is the last one
+                    self.done = page.len() < size;
+                    self.query.set_skip(self.query.skip() + page.len());
+                    self.remaining = self.remaining.map(|left| left - page.len());
+                    self.page = page.into_iter();
+                }
+            }
+        }
+    }
+}
+
+fn page_size(limit: Option<usize>) -> usize {
+    limit.map_or(QUERY_PAGE_SIZE, |limit| limit.min(QUERY_PAGE_SIZE))
+}
+
+// Runs jobs until every `AsyncStore` handing them out is dropped
+fn serve(store: &dyn GameStore, queue: &mut mpsc::UnboundedReceiver<Job>) {
+    while let Some(job) = queue.blocking_recv() {
+        job(store);
+    }
+}
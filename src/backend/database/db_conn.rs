@@ -1,13 +1,220 @@
-// TODO: Refactor to include a general DB Conn
+use mongodb::bson::DateTime;
 
-pub struct DBConn<T: DBEntry> {
+use super::integrity::{check_day, IntegrityReport};
+use super::query::{GameQuery, GamesIter, SortOrder};
+use super::stats::GameTotals;
+use super::trash::{put_back, TrashItem};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::CoreError as Error;
 
+// Storage for bowled games, every backend implements this so callers do not depend on one database
+pub trait GameStore {
+    // Adds `game` to the session under `key`, starting the session if it is not stored yet. A game
+    // number the session already has is a `DuplicateGameError`
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error>;
+
+    // Stores `games` as a new session after those already stored for its day, returning its number.
+    // Its game numbers have to be unique
+    fn add_games(&self, games: &Games) -> Result<u8, Error>;
+
+    fn get_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
+        Ok(self.get_session(key)?.and_then(|games| {
+            games
+                .games()
+                .iter()
+                .find(|g| g.game_num() == game_num)
+                .cloned()
+        }))
+    }
+
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error>;
+
+    // Every session of the day in order
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error>;
+
+    // Games bowled across every session of the day
+    fn num_games(&self, bowler: &Bowler, date: Date) -> Result<u8, Error> {
+        Ok(self
+            .get_games(bowler, date)?
+            .iter()
+            .map(|s| s.games().len() as u8)
+            .sum())
+    }
+
+    // Sessions matching `query` by day, then bowler, then session, read a day at a time
+    fn query(&self, query: &GameQuery) -> Result<GamesIter<'_>, Error> {
+        let mut days = Vec::new();
+
+        for bowler in self.bowlers()? {
+            if query.bowler().is_some_and(|b| *b != bowler) {
+                continue;
+            }
+
+            for date in self.dates(&bowler)? {
+                if query.matches_day(&bowler, date) {
+                    days.push((bowler.clone(), date));
+                }
+            }
+        }
+
+        days.sort_by(|(lb, ld), (rb, rd)| {
+            ld.partial_cmp(rd)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| lb.name().cmp(rb.name()))
+        });
+
+        let descending = query.order() == SortOrder::Descending;
+
+        if descending {
+            days.reverse();
+        }
+
+        let filter = query.clone();
+        let sessions = days
+            .into_iter()
+            .flat_map(move |(bowler, date)| match self.get_games(&bowler, date) {
+                Err(err) => vec![Err(err)],
+                Ok(mut sessions) => {
+                    if descending {
+                        sessions.reverse();
+                    }
+
+                    sessions.into_iter().map(Ok).collect()
+                }
+            })
+            .filter(move |games| games.as_ref().map_or(true, |g| filter.matches(g)));
+
+        Ok(Box::new(query.page(sessions)))
+    }
+
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error>;
+
+    fn modify_games(&self, games: &Games) -> Result<(), Error>;
+
+    // Removing moves what was removed to the trash, it only goes for good once purged
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error>;
+
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error>;
+
+    // Removes every session of the day, returning what was removed
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error>;
+
+    // Removed sessions and games that have not been purged yet, oldest first
+    fn trash(&self) -> Result<Vec<TrashItem>, Error>;
+
+    // Removes trash item `id` for good, returning whether there was one
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error>;
+
+    // Puts trash item `id` back and takes it out of the trash, returning the session it went to
+    fn restore_trashed(&self, id: u64) -> Result<Option<SessionKey>, Error> {
+        let item = match self.trash()?.into_iter().find(|item| item.id() == id) {
+            None => return Ok(None),
+            Some(item) => item,
+        };
+
+        let key = put_back(self, &item)?;
+        self.delete_trashed(id)?;
+
+        Ok(Some(key))
+    }
+
+    // Removes what was kept in the trash past its retention for good, returning how much went
+    fn purge_trash(&self) -> Result<u64, Error> {
+        let now = DateTime::now();
+        let mut purged = 0;
+
+        for item in self.trash()? {
+            if item.is_expired(now) && self.delete_trashed(item.id())? {
+                purged += 1;
+            }
+        }
+
+        Ok(purged)
+    }
+
+    // Adds up the games of the sessions matching `query`, no-tap games only count when
+    // `include_no_tap` is set like `Games::set_include_no_tap`
+    fn totals(&self, query: &GameQuery, include_no_tap: bool) -> Result<GameTotals, Error> {
+        fold_totals(self, query, include_no_tap)
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error>;
+
+    // Days `bowler` has games stored on, earliest first
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error>;
+
+    // Removes every session for good, the trash is left as it is
+    fn drop_all(&self) -> Result<(), Error>;
+
+    // Every stored session that is not a valid set of games and why, days that can not be read
+    // are reported by bowler and date
+    fn integrity_report(&self) -> Result<IntegrityReport, Error> {
+        let mut report = IntegrityReport::default();
+
+        for bowler in self.bowlers()? {
+            for date in self.dates(&bowler)? {
+                let id = format!("{} {}", bowler.name(), date_key(date));
+                check_day(&mut report, &id, self.get_games(&bowler, date));
+            }
+        }
+
+        Ok(report)
+    }
+
+    // Whether every stored session is a valid set of games
+    fn validate(&self) -> bool {
+        self.integrity_report()
+            .is_ok_and(|report| report.is_valid())
+    }
+}
+
+// Adds up the sessions `query` hands out one at a time
+pub(crate) fn fold_totals<S: GameStore + ?Sized>(
+    store: &S,
+    query: &GameQuery,
+    include_no_tap: bool,
+) -> Result<GameTotals, Error> {
+    store
+        .query(query)?
+        .try_fold(GameTotals::build(), |acc, games| {
+            let mut games = games?;
+            games.set_include_no_tap(include_no_tap);
+
+            Ok(acc.add(&GameTotals::build_from_games(&games)))
+        })
 }
 
-pub trait DBEntry {
+// First game number of `games` used by more than one of its games
+pub(crate) fn duplicate_game(games: &Games) -> Option<u8> {
+    let games = games.games();
 
+    games
+        .iter()
+        .enumerate()
+        .find(|(idx, game)| {
+            games[..*idx]
+                .iter()
+                .any(|g| g.game_num() == game.game_num())
+        })
+        .map(|(_, game)| game.game_num())
 }
 
-pub trait DBElement {
+// Dates are written as YYYY-MM-DD in stores keyed by text, so they sort in order
+pub(crate) fn date_key(date: Date) -> String {
+    format!(
+        "{:0>4}-{:0>2}-{:0>2}",
+        date.year(),
+        date.month(),
+        date.day()
+    )
+}
+
+pub(crate) fn parse_date_key(text: &str) -> Option<Date> {
+    let mut parts = text.splitn(3, '-');
+
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
 
+    Some(Date::build_with(year, month, day))
 }
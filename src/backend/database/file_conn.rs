@@ -0,0 +1,433 @@
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use super::db_conn::{date_key, duplicate_game, parse_date_key, GameStore};
+use super::integrity::{check_day, IntegrityReport};
+use super::trash::{next_id, TrashItem, DEFAULT_RETENTION};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+const LOCK_FILE: &str = ".lock";
+const TRASH_FILE: &str = ".trash.json";
+
+// Games kept as one JSON document per bowler and day under a directory
+// Holding the lock file means only one running instance writes to the directory
+pub struct FileConn {
+    dir: PathBuf,
+    _lock: File,
+    // How long removed games stay in the trash
+    retention: Duration,
+}
+
+impl FileConn {
+    // Constructor
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(LOCK_FILE))?;
+
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Err(Error::StoreLockedError(dir)),
+            Err(TryLockError::Error(err)) => return Err(err.into()),
+        };
+
+        Ok(FileConn {
+            dir,
+            _lock: lock,
+            retention: DEFAULT_RETENTION,
+        })
+    }
+
+    // Getter
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn trash_retention(&self) -> Duration {
+        self.retention
+    }
+
+    // Setter
+    // Applies to what is removed from now on
+    pub fn set_trash_retention(&mut self, retention: Duration) {
+        self.retention = retention;
+    }
+
+    // Method
+    fn bowler_dir(&self, bowler: &Bowler) -> PathBuf {
+        self.dir.join(encode_name(bowler.name()))
+    }
+
+    fn day_path(&self, bowler: &Bowler, date: Date) -> PathBuf {
+        self.bowler_dir(bowler)
+            .join(format!("{}.json", date_key(date)))
+    }
+
+    // Every session of the day in order, none when the day has no document
+    fn read_day(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        read_document(&self.day_path(bowler, date))
+    }
+
+    // Replaces the document of the day, removing it once no sessions are left
+    fn write_day(&self, bowler: &Bowler, date: Date, sessions: &[Games]) -> Result<(), Error> {
+        let path = self.day_path(bowler, date);
+
+        if sessions.is_empty() {
+            match fs::remove_file(&path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            };
+
+            // Drop the bowler once their last day is gone
+            let _ = fs::remove_dir(self.bowler_dir(bowler));
+
+            return Ok(());
+        }
+
+        fs::create_dir_all(self.bowler_dir(bowler))?;
+
+        let mut sessions = sessions.to_vec();
+        sessions.sort_by_key(Games::session_id);
+
+        write_atomic(
+            &path,
+            &serde_json::to_vec_pretty(&sessions).map_err(io::Error::from)?,
+        )
+    }
+
+    fn write_trash(&self, items: &[TrashItem]) -> Result<(), Error> {
+        write_atomic(
+            &self.dir.join(TRASH_FILE),
+            &serde_json::to_vec_pretty(items).map_err(io::Error::from)?,
+        )
+    }
+
+    // Writes the trash before the day is changed, and takes what was added out again when
+    // changing the day fails, so games are never lost in between
+    fn remove_to_trash(
+        &self,
+        bowler: &Bowler,
+        date: Date,
+        kept: &[Games],
+        removed: impl Fn(u64) -> Vec<TrashItem>,
+    ) -> Result<(), Error> {
+        let trash = self.trash()?;
+
+        let mut with_removed = trash.clone();
+        with_removed.extend(removed(next_id(&trash)));
+        self.write_trash(&with_removed)?;
+
+        if let Err(err) = self.write_day(bowler, date, kept) {
+            let _ = self.write_trash(&trash);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
+
+impl GameStore for FileConn {
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        let mut sessions = self.read_day(key.bowler(), key.date())?;
+
+        match sessions.iter_mut().find(|s| s.key() == key) {
+            None => sessions.push(Games::build_for(key.clone(), vec![game.clone()])),
+            Some(session) => {
+                if session
+                    .games()
+                    .iter()
+                    .any(|g| g.game_num() == game.game_num())
+                {
+                    return Err(Error::DuplicateGameError(key.clone(), game.game_num()));
+                }
+
+                session.add_game(game.clone());
+            }
+        };
+
+        self.write_day(key.bowler(), key.date(), &sessions)
+    }
+
+    fn add_games(&self, games: &Games) -> Result<u8, Error> {
+        let mut sessions = self.read_day(games.bowler(), games.date())?;
+
+        let session_id = sessions.iter().map(Games::session_id).max().unwrap_or(0) + 1;
+
+        let key = SessionKey::build(games.bowler().clone(), games.date(), session_id);
+
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(key, game_num));
+        }
+
+        let mut stored = games.clone();
+        stored.set_key(key);
+
+        sessions.push(stored);
+        self.write_day(games.bowler(), games.date(), &sessions)?;
+
+        Ok(session_id)
+    }
+
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        Ok(self
+            .read_day(key.bowler(), key.date())?
+            .into_iter()
+            .find(|s| s.key() == key))
+    }
+
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        self.read_day(bowler, date)
+    }
+
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        let mut sessions = self.read_day(key.bowler(), key.date())?;
+
+        let stored = sessions
+            .iter_mut()
+            .find(|s| s.key() == key)
+            .and_then(|s| {
+                s.games_mut()
+                    .iter_mut()
+                    .find(|g| g.game_num() == game.game_num())
+            })
+            .ok_or_else(|| Error::GameNotFoundError(key.clone(), game.game_num()))?;
+
+        *stored = game.clone();
+
+        self.write_day(key.bowler(), key.date(), &sessions)
+    }
+
+    fn modify_games(&self, games: &Games) -> Result<(), Error> {
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(games.key().clone(), game_num));
+        }
+
+        let mut sessions = self.read_day(games.bowler(), games.date())?;
+
+        let stored = sessions
+            .iter_mut()
+            .find(|s| s.key() == games.key())
+            .ok_or_else(|| Error::SessionNotFoundError(games.key().clone()))?;
+
+        *stored = games.clone();
+
+        self.write_day(games.bowler(), games.date(), &sessions)
+    }
+
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
+        let mut sessions = self.read_day(key.bowler(), key.date())?;
+
+        let session = match sessions.iter_mut().find(|s| s.key() == key) {
+            None => return Ok(None),
+            Some(session) => session,
+        };
+
+        let before = session.clone();
+        let game = match session.remove_game(game_num) {
+            None => return Ok(None),
+            Some(game) => game,
+        };
+
+        self.remove_to_trash(key.bowler(), key.date(), &sessions, |id| {
+            vec![TrashItem::build_game(id, &before, &game, self.retention)]
+        })?;
+
+        Ok(Some(game))
+    }
+
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        let sessions = self.read_day(key.bowler(), key.date())?;
+
+        let (removed, kept): (Vec<Games>, Vec<Games>) =
+            sessions.into_iter().partition(|s| s.key() == key);
+
+        let removed = match removed.into_iter().next() {
+            None => return Ok(None),
+            Some(removed) => removed,
+        };
+
+        self.remove_to_trash(key.bowler(), key.date(), &kept, |id| {
+            vec![TrashItem::build_session(id, &removed, self.retention)]
+        })?;
+
+        Ok(Some(removed))
+    }
+
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        let sessions = self.read_day(bowler, date)?;
+
+        if !sessions.is_empty() {
+            self.remove_to_trash(bowler, date, &[], |first| {
+                (first..)
+                    .zip(&sessions)
+                    .map(|(id, games)| TrashItem::build_session(id, games, self.retention))
+                    .collect()
+            })?;
+        }
+
+        Ok(sessions)
+    }
+
+    fn trash(&self) -> Result<Vec<TrashItem>, Error> {
+        match fs::read(self.dir.join(TRASH_FILE)) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).map_err(io::Error::from)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error> {
+        let mut trash = self.trash()?;
+        let before = trash.len();
+
+        trash.retain(|item| item.id() != id);
+
+        if trash.len() == before {
+            return Ok(false);
+        }
+
+        self.write_trash(&trash)?;
+
+        Ok(true)
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error> {
+        let mut bowlers = Vec::new();
+
+        for bowler_dir in sorted_entries(&self.dir)? {
+            if !bowler_dir.is_dir() {
+                continue;
+            }
+
+            if let Some(name) = bowler_dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(decode_name)
+            {
+                bowlers.push(Bowler::build(&name));
+            }
+        }
+
+        Ok(bowlers)
+    }
+
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error> {
+        let bowler_dir = self.bowler_dir(bowler);
+
+        if !bowler_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        Ok(sorted_entries(&bowler_dir)?
+            .iter()
+            .filter_map(|path| day_of_file(path))
+            .collect())
+    }
+
+    // Days that can not be read are reported by the path of their document
+    fn integrity_report(&self) -> Result<IntegrityReport, Error> {
+        let mut report = IntegrityReport::default();
+
+        for bowler in self.bowlers()? {
+            for date in self.dates(&bowler)? {
+                let path = self.day_path(&bowler, date);
+                check_day(
+                    &mut report,
+                    &path.display().to_string(),
+                    read_document(&path),
+                );
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn drop_all(&self) -> Result<(), Error> {
+        for bowler_dir in sorted_entries(&self.dir)? {
+            if bowler_dir.is_dir() {
+                fs::remove_dir_all(bowler_dir)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_document(path: &Path) -> Result<Vec<Games>, Error> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(serde_json::from_slice(&bytes).map_err(io::Error::from)?)
+}
+
+// Writes next to `path` and renames over it, so readers see the old or the new document in full
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut tmp = File::create(&tmp_path)?;
+    tmp.write_all(contents)?;
+    tmp.sync_all()?;
+
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    entries.sort();
+
+    Ok(entries)
+}
+
+// Bowler names become directory names, anything but letters, digits, `-` and `_` is escaped as %XX
+fn encode_name(name: &str) -> String {
+    name.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn decode_name(dir_name: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut chars = dir_name.bytes();
+
+    while let Some(b) = chars.next() {
+        if b == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+// Day documents are named after their date
+fn day_of_file(path: &Path) -> Option<Date> {
+    if path.extension()? != "json" {
+        return None;
+    }
+
+    parse_date_key(path.file_stem()?.to_str()?)
+}
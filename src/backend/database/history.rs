@@ -0,0 +1,135 @@
+use mongodb::bson::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::core::types::{Games, SessionKey};
+
+// Store call that made a change
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operation {
+    AddGame,
+    AddGames,
+    ModifyGame,
+    ModifyGames,
+    RemoveGame,
+    RemoveSession,
+    RemoveGames,
+    DropAll,
+    RestoreSession,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Edit(Operation),
+    // Reverted the change with this number
+    Undo(u64),
+    // Reverted the undo with this number
+    Redo(u64),
+}
+
+// A session as it was before and after a change, `None` when it was not stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionChange {
+    key: SessionKey,
+    before: Option<Games>,
+    after: Option<Games>,
+}
+
+impl SessionChange {
+    // Constructor
+    pub fn build(key: SessionKey, before: Option<Games>, after: Option<Games>) -> Self {
+        Self { key, before, after }
+    }
+
+    // Getter
+    pub fn key(&self) -> &SessionKey {
+        &self.key
+    }
+
+    pub fn before(&self) -> Option<&Games> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&Games> {
+        self.after.as_ref()
+    }
+
+    // Method
+    // The change that takes the session back
+    pub fn reversed(&self) -> Self {
+        Self::build(self.key.clone(), self.after.clone(), self.before.clone())
+    }
+}
+
+// One entry of the change log, entries are only ever added
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Change {
+    #[serde(rename = "_id")]
+    seq: u64,
+    by: String,
+    at: DateTime,
+    kind: ChangeKind,
+    sessions: Vec<SessionChange>,
+}
+
+impl Change {
+    // Constructor
+    pub fn build(seq: u64, by: &str, kind: ChangeKind, sessions: Vec<SessionChange>) -> Self {
+        Self {
+            seq,
+            by: by.to_string(),
+            at: DateTime::now(),
+            kind,
+            sessions,
+        }
+    }
+
+    // Getter
+    // Changes are numbered from 1 in the order they were made
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    pub fn by(&self) -> &str {
+        &self.by
+    }
+
+    pub fn at(&self) -> DateTime {
+        self.at
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    pub fn sessions(&self) -> &[SessionChange] {
+        &self.sessions
+    }
+}
+
+// Latest edit or redo that has not been undone, `log` is in order
+pub fn undo_target(log: &[Change]) -> Option<&Change> {
+    log.iter().rev().find(|change| {
+        matches!(change.kind, ChangeKind::Edit(_) | ChangeKind::Redo(_))
+            && !log.iter().any(|c| c.kind == ChangeKind::Undo(change.seq))
+    })
+}
+
+// Latest undo that has not been redone, as long as nothing was edited after it
+pub fn redo_target(log: &[Change]) -> Option<&Change> {
+    log.iter()
+        .rev()
+        .take_while(|change| !matches!(change.kind, ChangeKind::Edit(_)))
+        .find(|change| {
+            matches!(change.kind, ChangeKind::Undo(_))
+                && !log.iter().any(|c| c.kind == ChangeKind::Redo(change.seq))
+        })
+}
+
+// Latest copy of the session under `key` from before it was removed
+pub fn removed_session<'a>(log: &'a [Change], key: &SessionKey) -> Option<&'a Games> {
+    log.iter()
+        .rev()
+        .flat_map(|change| change.sessions.iter())
+        .find(|session| &session.key == key && session.after.is_none())
+        .and_then(SessionChange::before)
+}
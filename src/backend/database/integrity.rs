@@ -0,0 +1,238 @@
+use crate::backend::core::types::{Frame, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+// Why a stored session is not a valid set of games
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    // The stored data does not decode into a session
+    Undecodable(String),
+    // Stored under its date alone from before there were bowlers, `DatabaseConn::migrate` moves it
+    Unmigrated,
+    // Game numbers do not run 1, 2, 3, ... in the order the games are stored
+    GameOrder,
+    DuplicateGame(u8),
+    // Game and frame number of a frame that was never bowled
+    UnplayedFrame(u8, u8),
+    // Game and frame number of a frame the variant does not allow there
+    InvalidFrame(u8, u8),
+    // Every frame is fine on its own but the game is not complete under the session's rules
+    InvalidGame(u8),
+}
+
+impl Issue {
+    // Renumbering the games fixes it without touching any frames
+    pub fn is_numbering(&self) -> bool {
+        matches!(self, Self::GameOrder | Self::DuplicateGame(_))
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Undecodable(why) => write!(f, "can not be read: {}", why),
+            Self::Unmigrated => write!(f, "is not stored under a bowler yet"),
+            Self::GameOrder => write!(f, "games are not numbered in order"),
+            Self::DuplicateGame(game_num) => write!(f, "game {} is stored twice", game_num),
+            Self::UnplayedFrame(game_num, frame_no) => {
+                write!(f, "frame {} of game {} was not bowled", frame_no, game_num)
+            }
+            Self::InvalidFrame(game_num, frame_no) => {
+                write!(f, "frame {} of game {} is not valid", frame_no, game_num)
+            }
+            Self::InvalidGame(game_num) => write!(f, "game {} is not complete", game_num),
+        }
+    }
+}
+
+// What to do with the sessions that are not valid
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum RepairMode {
+    // Only report them
+    #[default]
+    Report,
+    // Renumber games where that is all that is wrong and move everything else aside
+    Repair,
+}
+
+// A stored session that failed the check, `key` is missing when it could not be decoded
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidSession {
+    id: String,
+    key: Option<SessionKey>,
+    issues: Vec<Issue>,
+}
+
+impl InvalidSession {
+    // Constructor
+    pub fn build(id: &str, key: Option<SessionKey>, issues: Vec<Issue>) -> Self {
+        Self {
+            id: id.to_string(),
+            key,
+            issues,
+        }
+    }
+
+    // Getter
+    // How the store identifies the session
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn key(&self) -> Option<&SessionKey> {
+        self.key.as_ref()
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+}
+
+// Outcome of checking every session of a store
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IntegrityReport {
+    checked: usize,
+    invalid: Vec<InvalidSession>,
+    renumbered: Vec<SessionKey>,
+    quarantined: Vec<String>,
+}
+
+impl IntegrityReport {
+    // Getter
+    pub fn checked(&self) -> usize {
+        self.checked
+    }
+
+    pub fn invalid(&self) -> &[InvalidSession] {
+        &self.invalid
+    }
+
+    // Sessions whose games were renumbered in place
+    pub fn renumbered(&self) -> &[SessionKey] {
+        &self.renumbered
+    }
+
+    // Ids of the sessions moved out of the store
+    pub fn quarantined(&self) -> &[String] {
+        &self.quarantined
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.invalid.is_empty()
+    }
+
+    // Method
+    pub(crate) fn add_checked(&mut self) {
+        self.checked += 1;
+    }
+
+    pub(crate) fn add_invalid(&mut self, invalid: InvalidSession) {
+        self.invalid.push(invalid);
+    }
+
+    pub(crate) fn add_renumbered(&mut self, key: SessionKey) {
+        self.renumbered.push(key);
+    }
+
+    pub(crate) fn add_quarantined(&mut self, id: &str) {
+        self.quarantined.push(id.to_string());
+    }
+}
+
+// Everything wrong with `games`, empty when it is valid
+pub fn check_games(games: &Games) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if games
+        .games()
+        .iter()
+        .zip(1..)
+        .any(|(g, n)| g.game_num() != n)
+    {
+        issues.push(Issue::GameOrder);
+    }
+
+    let mut seen = Vec::new();
+
+    for game in games.games() {
+        let game_num = game.game_num();
+
+        if seen.contains(&game_num) {
+            issues.push(Issue::DuplicateGame(game_num));
+        } else {
+            seen.push(game_num);
+        }
+
+        let mut frames_valid = true;
+
+        // Checked as scored, so no-tap frames count as the strikes they stand for
+        for (frame, frame_no) in game.effective_frames().iter().zip(1..) {
+            if matches!(frame, Frame::Uninit) {
+                issues.push(Issue::UnplayedFrame(game_num, frame_no));
+                frames_valid = false;
+            } else if !frame.is_valid_no_with(game.variant(), frame_no) {
+                issues.push(Issue::InvalidFrame(game_num, frame_no));
+                frames_valid = false;
+            }
+        }
+
+        if frames_valid && !game.is_valid_with(games.rules()) {
+            issues.push(Issue::InvalidGame(game_num));
+        }
+    }
+
+    issues
+}
+
+// Checks the sessions of one day, a day the store could not read is reported as a whole under `id`
+pub(crate) fn check_day(
+    report: &mut IntegrityReport,
+    id: &str,
+    sessions: Result<Vec<Games>, Error>,
+) {
+    let sessions = match sessions {
+        Err(err) => {
+            report.add_checked();
+            report.add_invalid(InvalidSession::build(
+                id,
+                None,
+                vec![Issue::Undecodable(err.to_string())],
+            ));
+
+            return;
+        }
+        Ok(sessions) => sessions,
+    };
+
+    for games in sessions {
+        report.add_checked();
+
+        let issues = check_games(&games);
+
+        if !issues.is_empty() {
+            let id = format!("{} #{}", id, games.session_id());
+            report.add_invalid(InvalidSession::build(
+                &id,
+                Some(games.key().clone()),
+                issues,
+            ));
+        }
+    }
+}
+
+// `games` numbered 1, 2, 3, ... in game number order, when that is all that makes it invalid
+pub fn renumbered(games: &Games) -> Option<Games> {
+    let issues = check_games(games);
+
+    if issues.is_empty() || !issues.iter().all(Issue::is_numbering) {
+        return None;
+    }
+
+    let mut games = games.clone();
+    games.games_mut().sort_by_key(|g| g.game_num());
+
+    for (game, n) in games.games_mut().iter_mut().zip(1..) {
+        *game.game_num_mut() = n;
+    }
+
+    Some(games)
+}
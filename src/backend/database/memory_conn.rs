@@ -0,0 +1,239 @@
+use std::{cell::RefCell, collections::BTreeMap, time::Duration};
+
+use super::db_conn::{date_key, duplicate_game, parse_date_key, GameStore};
+use super::trash::{next_id, TrashItem, DEFAULT_RETENTION};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+type StoredKey = (String, String, u8);
+
+// Games kept only for the life of the store, for tests and trying the tracker out
+pub struct MemoryConn {
+    sessions: RefCell<BTreeMap<StoredKey, Games>>,
+    trash: RefCell<Vec<TrashItem>>,
+    // How long removed games stay in the trash
+    retention: Duration,
+}
+
+impl MemoryConn {
+    // Constructor
+    pub fn build() -> Self {
+        Self {
+            sessions: RefCell::default(),
+            trash: RefCell::default(),
+            retention: DEFAULT_RETENTION,
+        }
+    }
+
+    // Getter
+    pub fn trash_retention(&self) -> Duration {
+        self.retention
+    }
+
+    // Setter
+    // Applies to what is removed from now on
+    pub fn set_trash_retention(&mut self, retention: Duration) {
+        self.retention = retention;
+    }
+
+    // Method
+    fn move_to_trash(&self, item: impl FnOnce(u64) -> TrashItem) {
+        let mut trash = self.trash.borrow_mut();
+        let id = next_id(&trash);
+
+        trash.push(item(id));
+    }
+}
+
+impl Default for MemoryConn {
+    fn default() -> Self {
+        Self::build()
+    }
+}
+
+impl GameStore for MemoryConn {
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        let mut sessions = self.sessions.borrow_mut();
+        let session = sessions
+            .entry(stored_key(key))
+            .or_insert_with(|| Games::build_for(key.clone(), Vec::new()));
+
+        if session
+            .games()
+            .iter()
+            .any(|g| g.game_num() == game.game_num())
+        {
+            return Err(Error::DuplicateGameError(key.clone(), game.game_num()));
+        }
+
+        session.add_game(game.clone());
+
+        Ok(())
+    }
+
+    fn add_games(&self, games: &Games) -> Result<u8, Error> {
+        let session_id = self
+            .get_games(games.bowler(), games.date())?
+            .iter()
+            .map(Games::session_id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let key = SessionKey::build(games.bowler().clone(), games.date(), session_id);
+
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(key, game_num));
+        }
+
+        let mut games = games.clone();
+        games.set_key(key);
+
+        self.sessions
+            .borrow_mut()
+            .insert(stored_key(games.key()), games);
+
+        Ok(session_id)
+    }
+
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        Ok(self.sessions.borrow().get(&stored_key(key)).cloned())
+    }
+
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        let (name, day) = (bowler.name(), date_key(date));
+
+        Ok(self
+            .sessions
+            .borrow()
+            .iter()
+            .filter(|((n, d, _), _)| n == name && *d == day)
+            .map(|(_, games)| games.clone())
+            .collect())
+    }
+
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        let mut sessions = self.sessions.borrow_mut();
+
+        let stored = sessions
+            .get_mut(&stored_key(key))
+            .and_then(|s| {
+                s.games_mut()
+                    .iter_mut()
+                    .find(|g| g.game_num() == game.game_num())
+            })
+            .ok_or_else(|| Error::GameNotFoundError(key.clone(), game.game_num()))?;
+
+        *stored = game.clone();
+
+        Ok(())
+    }
+
+    fn modify_games(&self, games: &Games) -> Result<(), Error> {
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(games.key().clone(), game_num));
+        }
+
+        let mut sessions = self.sessions.borrow_mut();
+
+        let stored = sessions
+            .get_mut(&stored_key(games.key()))
+            .ok_or_else(|| Error::SessionNotFoundError(games.key().clone()))?;
+
+        *stored = games.clone();
+
+        Ok(())
+    }
+
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
+        let mut sessions = self.sessions.borrow_mut();
+
+        let session = match sessions.get_mut(&stored_key(key)) {
+            None => return Ok(None),
+            Some(session) => session,
+        };
+
+        let before = session.clone();
+        let game = session.remove_game(game_num);
+
+        if let Some(game) = &game {
+            self.move_to_trash(|id| TrashItem::build_game(id, &before, game, self.retention));
+        }
+
+        Ok(game)
+    }
+
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        let removed = self.sessions.borrow_mut().remove(&stored_key(key));
+
+        if let Some(games) = &removed {
+            self.move_to_trash(|id| TrashItem::build_session(id, games, self.retention));
+        }
+
+        Ok(removed)
+    }
+
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        let sessions = self.get_games(bowler, date)?;
+
+        for games in &sessions {
+            self.sessions.borrow_mut().remove(&stored_key(games.key()));
+            self.move_to_trash(|id| TrashItem::build_session(id, games, self.retention));
+        }
+
+        Ok(sessions)
+    }
+
+    fn trash(&self) -> Result<Vec<TrashItem>, Error> {
+        Ok(self.trash.borrow().clone())
+    }
+
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error> {
+        let mut trash = self.trash.borrow_mut();
+        let before = trash.len();
+
+        trash.retain(|item| item.id() != id);
+
+        Ok(trash.len() != before)
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error> {
+        let mut names = self
+            .sessions
+            .borrow()
+            .keys()
+            .map(|(name, _, _)| name.clone())
+            .collect::<Vec<String>>();
+        names.dedup();
+
+        Ok(names.iter().map(|name| Bowler::build(name)).collect())
+    }
+
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error> {
+        let mut days = self
+            .sessions
+            .borrow()
+            .keys()
+            .filter(|(name, _, _)| name == bowler.name())
+            .map(|(_, day, _)| day.clone())
+            .collect::<Vec<String>>();
+        days.dedup();
+
+        Ok(days.iter().filter_map(|day| parse_date_key(day)).collect())
+    }
+
+    fn drop_all(&self) -> Result<(), Error> {
+        self.sessions.borrow_mut().clear();
+
+        Ok(())
+    }
+}
+
+// Sorts by bowler, then day, then session
+fn stored_key(key: &SessionKey) -> StoredKey {
+    (
+        key.bowler().name().to_string(),
+        date_key(key.date()),
+        key.session_id(),
+    )
+}
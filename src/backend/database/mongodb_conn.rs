@@ -1,34 +1,191 @@
+use std::{env, thread, time::Duration};
+
 use mongodb::{
-    bson::{doc, to_bson},
+    bson::{doc, from_bson, from_document, to_bson, to_document, Bson, DateTime, Document},
+    error::ErrorKind,
+    options::{ClientOptions, ConnectionString, FindOneOptions, FindOptions, ReplaceOptions},
     sync::{Client, Collection, Database},
+    IndexModel,
 };
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 
-use crate::backend::core::types::{Date, Game, Games};
-use crate::error::CoreError as Error; // TODO: Should I keep this alias?
+use super::db_conn::{duplicate_game, fold_totals, GameStore};
+use super::history::{
+    redo_target, removed_session, undo_target, Change, ChangeKind, Operation, SessionChange,
+};
+use super::integrity::{
+    check_games, renumbered, IntegrityReport, InvalidSession, Issue, RepairMode,
+};
+use super::query::{GameQuery, GamesIter, SortOrder};
+use super::stats::GameTotals;
+use super::trash::{is_restored, numbered_for, TrashItem, DEFAULT_RETENTION};
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::{ConnectStep, CoreError as Error}; // TODO: Should I keep this alias?
+
+// How long to wait for the server and how often to try again
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConnectOptions {
+    timeout: Duration,
+    retries: u8,
+    backoff: Duration,
+}
 
-pub struct DatabaseConn {
-    client: Client,
-    database: Option<Database>,
-    collection: Option<Collection<Games>>,
+impl ConnectOptions {
+    // Constructor
+    pub fn build() -> Self {
+        Self::build_with(Duration::from_secs(5), 2, Duration::from_millis(500))
+    }
+
+    // `backoff` is the wait before the first retry, doubling for each one after
+    pub fn build_with(timeout: Duration, retries: u8, backoff: Duration) -> Self {
+        Self {
+            timeout,
+            retries,
+            backoff,
+        }
+    }
+
+    // Getter
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn retries(&self) -> u8 {
+        self.retries
+    }
+
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
 }
 
-impl DatabaseConn {
-    pub fn connect(user: &str, pass: &str, host: &str, port: &str, auth_db: &str) -> Self {
-        let client =
-            Client::with_uri_str(format!("mongodb://{user}:{pass}@{host}:{port}/{auth_db}"))
-                .expect("Unable to connect to MongoDB");
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self::build()
+    }
+}
 
-        if let Err(err) = client.list_database_names(None, None) {
-            panic!("{}", err.to_string());
-        };
+// Characters a user name or password can hold in a connection string without being encoded
+const USERINFO: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+// Everything needed to reach a MongoDB server, and optionally a database and collection on it
+#[derive(Clone, PartialEq, Eq)]
+pub struct MongoSettings {
+    address: String,
+    database: Option<String>,
+    collection: Option<String>,
+    options: ConnectOptions,
+}
 
-        DatabaseConn {
-            client,
+impl MongoSettings {
+    // Constructor
+    // `user` and `pass` may hold any characters, they are encoded for the connection string
+    pub fn build(user: &str, pass: &str, host: &str, port: &str, auth_db: &str) -> Self {
+        let user = utf8_percent_encode(user, USERINFO);
+        let pass = utf8_percent_encode(pass, USERINFO);
+
+        Self::build_from_uri(&format!("mongodb://{user}:{pass}@{host}:{port}/{auth_db}"))
+    }
+
+    // Any connection string the driver accepts, such as `mongodb+srv` or a replica set
+    pub fn build_from_uri(uri: &str) -> Self {
+        Self {
+            address: uri.to_string(),
             database: None,
             collection: None,
+            options: ConnectOptions::build(),
         }
     }
 
+    // Getter
+    pub fn database(&self) -> Option<&str> {
+        self.database.as_deref()
+    }
+
+    pub fn collection(&self) -> Option<&str> {
+        self.collection.as_deref()
+    }
+
+    pub fn options(&self) -> ConnectOptions {
+        self.options
+    }
+
+    // The address with the timeouts added, unless it already sets them
+    pub(crate) fn uri(&self) -> String {
+        let timeout = self.options.timeout.as_millis();
+        let mut uri = self.address.clone();
+
+        // Options have to follow a slash after the hosts, even when there is no database
+        let hosts = uri.split_once("://").map_or(uri.as_str(), |(_, rest)| rest);
+
+        if !hosts.contains('/') && !hosts.contains('?') {
+            uri.push('/');
+        }
+
+        for option in ["connectTimeoutMS", "serverSelectionTimeoutMS"] {
+            if uri.contains(&format!("{option}=")) {
+                continue;
+            }
+
+            uri.push(if uri.contains('?') { '&' } else { '?' });
+            uri.push_str(&format!("{option}={timeout}"));
+        }
+
+        uri
+    }
+
+    // Setter
+    pub fn set_database(&mut self, database: Option<&str>) {
+        self.database = database.map(str::to_string);
+    }
+
+    pub fn set_collection(&mut self, collection: Option<&str>) {
+        self.collection = collection.map(str::to_string);
+    }
+
+    pub fn set_options(&mut self, options: ConnectOptions) {
+        self.options = options;
+    }
+}
+
+// The password is left out so settings can be logged
+impl std::fmt::Debug for MongoSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MongoSettings")
+            .field("address", &redacted_uri(&self.address))
+            .field("database", &self.database)
+            .field("collection", &self.collection)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+pub struct DatabaseConn {
+    client: Client,
+    database: Option<Database>,
+    collection: Option<Collection<Games>>,
+    // Who the change log records changes as
+    author: String,
+    // How long removed games stay in the trash
+    retention: Duration,
+}
+
+impl DatabaseConn {
+    pub fn connect(
+        user: &str,
+        pass: &str,
+        host: &str,
+        port: &str,
+        auth_db: &str,
+    ) -> Result<Self, Error> {
+        Self::connect_with(&MongoSettings::build(user, pass, host, port, auth_db))
+    }
+
     pub fn connect_db(
         user: &str,
         pass: &str,
@@ -36,21 +193,11 @@ impl DatabaseConn {
         port: &str,
         auth_db: &str,
         db: &str,
-    ) -> Self {
-        let client =
-            Client::with_uri_str(format!("mongodb://{user}:{pass}@{host}:{port}/{auth_db}"))
-                .expect("Unable to connect to MongoDB");
+    ) -> Result<Self, Error> {
+        let mut settings = MongoSettings::build(user, pass, host, port, auth_db);
+        settings.set_database(Some(db));
 
-        let database = client.database(db);
-        if let Err(err) = database.list_collection_names(None) {
-            panic!("{}", err.to_string());
-        };
-
-        DatabaseConn {
-            client,
-            database: Some(database),
-            collection: None,
-        }
+        Self::connect_with(&settings)
     }
 
     pub fn connect_full(
@@ -61,39 +208,123 @@ impl DatabaseConn {
         auth_db: &str,
         db: &str,
         coll: &str,
-    ) -> Self {
-        let client =
-            Client::with_uri_str(format!("mongodb://{user}:{pass}@{host}:{port}/{auth_db}"))
-                .expect("Unable to connect to MongoDB");
+    ) -> Result<Self, Error> {
+        let mut settings = MongoSettings::build(user, pass, host, port, auth_db);
+        settings.set_database(Some(db));
+        settings.set_collection(Some(coll));
+
+        Self::connect_with(&settings)
+    }
 
-        let database = client.database(db);
-        if let Err(err) = database.list_collection_names(None) {
-            panic!("{}", err.to_string());
+    // Checks each step of reaching the collection in turn, so a failure names the step
+    pub fn connect_with(settings: &MongoSettings) -> Result<Self, Error> {
+        let options = settings.options();
+        let uri = settings.uri();
+
+        // A malformed address fails here, apart from a host that can not be found
+        retry(options, ConnectStep::Parse, || {
+            ConnectionString::parse(&uri)
+        })?;
+
+        // `mongodb+srv` addresses are looked up while the options are built
+        let client_options = retry(options, ConnectStep::Resolve, || ClientOptions::parse(&uri))?;
+
+        let client = retry(options, ConnectStep::Parse, || {
+            Client::with_options(client_options.clone())
+        })?;
+
+        // The client only connects when first used, so an unknown host or a server that does not
+        // answer shows up here instead of on the listing below. A rejected login is told apart
+        retry(options, ConnectStep::Resolve, || {
+            client
+                .database("admin")
+                .run_command(doc! { "ping": 1 }, None)
+        })?;
+
+        let database = match settings.database() {
+            None => {
+                retry(options, ConnectStep::ListDatabases, || {
+                    client.list_database_names(None, None)
+                })?;
+
+                None
+            }
+            Some(name) => {
+                let database = client.database(name);
+
+                retry(options, ConnectStep::ListCollections, || {
+                    database.list_collection_names(None)
+                })?;
+
+                Some(database)
+            }
         };
 
-        let collection = database.collection(coll);
-        if let Err(err) = collection.list_index_names() {
-            panic!("{}", err.to_string());
+        let collection = match (&database, settings.collection()) {
+            (Some(database), Some(name)) => {
+                let collection = database.collection::<Games>(name);
+
+                retry(options, ConnectStep::AccessCollection, || {
+                    collection.list_index_names()
+                })?;
+                retry(options, ConnectStep::AccessCollection, || {
+                    create_indexes(&collection)
+                })?;
+
+                Some(collection)
+            }
+            _ => None,
         };
 
-        DatabaseConn {
+        Ok(DatabaseConn {
             client,
-            database: Some(database),
-            collection: Some(collection),
-        }
+            database,
+            collection,
+            author: env::var("USER")
+                .or_else(|_| env::var("USERNAME"))
+                .unwrap_or_else(|_| String::from("unknown")),
+            retention: DEFAULT_RETENTION,
+        })
+    }
+
+    // Getter
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn trash_retention(&self) -> Duration {
+        self.retention
+    }
+
+    // Stored documents as they are, for tests that write what older versions stored
+    #[cfg(test)]
+    pub(crate) fn documents(&self) -> Option<Collection<Document>> {
+        self.collection
+            .as_ref()
+            .map(|coll| coll.clone_with_type::<Document>())
+    }
+
+    // Setter
+    pub fn set_author(&mut self, author: &str) {
+        self.author = author.to_string();
+    }
+
+    // Applies to what is removed from now on
+    pub fn set_trash_retention(&mut self, retention: Duration) {
+        self.retention = retention;
     }
 
     pub fn set_database(&mut self, name: &str) -> Result<(), Error> {
         let database = self.client.database(name);
         // Check that the user has access to the database being set
         if database.list_collection_names(None).is_err() {
-            return Err(Error::DBConnError);
+            return Err(Error::AccessDeniedError(name.to_string()));
         };
 
         // Check that the database being set is not the same
         if let Some(db) = &self.database {
             if database.name() == db.name() {
-                return Err(Error::DBConnError);
+                return Err(Error::SameDatabaseError(name.to_string()));
             }
         };
 
@@ -105,7 +336,7 @@ impl DatabaseConn {
 
     pub fn unset_database(&mut self) -> Result<(), Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         self.database = None;
@@ -116,12 +347,12 @@ impl DatabaseConn {
 
     pub fn set_collection(&mut self, name: &str) -> Result<(), Error> {
         let collection = match &self.database {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::DatabaseNotAssignedError),
             Some(database) => database.collection::<Games>(name),
         };
 
-        if collection.list_index_names().is_err() {
-            return Err(Error::DBConnError);
+        if collection.list_index_names().is_err() || create_indexes(&collection).is_err() {
+            return Err(Error::AccessDeniedError(name.to_string()));
         };
 
         self.collection = Some(collection);
@@ -131,7 +362,7 @@ impl DatabaseConn {
 
     pub fn unset_collection(&mut self) -> Result<(), Error> {
         if self.collection.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::CollectionNotAssignedError);
         };
 
         self.collection = None;
@@ -139,209 +370,598 @@ impl DatabaseConn {
         Ok(())
     }
 
-    pub fn add_game(&self, date: Date, game: &Game) -> Result<(), Error> {
+    // Moves documents keyed only by their date to `bowler`, returning how many were moved.
+    // Each becomes the next session of its day, after any `bowler` already has
+    pub fn migrate(&self, bowler: &Bowler) -> Result<u64, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
-            Some(coll) => coll,
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll.clone_with_type::<Document>(),
         };
 
-        let filter = doc! {
-            "_id": to_bson(&date).unwrap(),
+        let legacy = coll
+            .find(doc! { "_id.bowler": doc! { "$exists": false } }, None)?
+            .collect::<Result<Vec<Document>, _>>()?;
+
+        let mut moved = 0;
+
+        for old in legacy {
+            let old_id = old
+                .get("_id")
+                .cloned()
+                .ok_or_else(|| Error::CorruptDataError(String::from("document without an _id")))?;
+
+            let date = from_bson::<Date>(old_id.clone())
+                .map_err(|_| Error::CorruptDataError(format!("_id {} is not a date", old_id)))?;
+            let mut document = migrate_document(old, bowler)?;
+
+            let session_id = self
+                .get_games(bowler, date)?
+                .iter()
+                .map(Games::session_id)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            let key = SessionKey::build(bowler.clone(), date, session_id);
+            document.insert("_id", to_bson(&key).unwrap());
+
+            // Written before the old one is removed so nothing is lost when either step fails
+            coll.insert_one(with_totals(document), None)?;
+            coll.delete_one(doc! { "_id": old_id }, None)?;
+
+            moved += 1;
+        }
+
+        Ok(moved)
+    }
+
+    // Every change in the order it was made, kept in `<collection>_changes`
+    pub fn history(&self) -> Result<Vec<Change>, Error> {
+        let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+
+        Ok(self
+            .changes()?
+            .find(doc! {}, options)?
+            .collect::<Result<Vec<Change>, _>>()?)
+    }
+
+    // Reverts the latest change that is not undone yet, returning what was undone
+    pub fn undo_last(&self) -> Result<Option<Change>, Error> {
+        let history = self.history()?;
+
+        match undo_target(&history) {
+            None => Ok(None),
+            Some(change) => {
+                self.revert(change, ChangeKind::Undo(change.seq()))?;
+                Ok(Some(change.clone()))
+            }
+        }
+    }
+
+    // Reverts the latest undo when nothing was changed since, returning the undo that was reverted
+    pub fn redo_last(&self) -> Result<Option<Change>, Error> {
+        let history = self.history()?;
+
+        match redo_target(&history) {
+            None => Ok(None),
+            Some(change) => {
+                self.revert(change, ChangeKind::Redo(change.seq()))?;
+                Ok(Some(change.clone()))
+            }
+        }
+    }
+
+    // Stores the last removed copy of the session under `key` again, after the sessions of its
+    // day when its number has been taken since, returning where it was put
+    pub fn restore_session(&self, key: &SessionKey) -> Result<Option<SessionKey>, Error> {
+        let history = self.history()?;
+
+        let games = match removed_session(&history, key) {
+            None => return Ok(None),
+            Some(games) => games,
         };
 
-        // Check for existing games
-        match coll.find_one(filter, None)? {
+        let restored = self.put_back(games)?;
+        self.forget_restored(games)?;
+
+        Ok(Some(restored))
+    }
+
+    // Stores a removed session under its own key, or after the sessions of its day when its
+    // number has been taken since, returning where it was put
+    fn put_back(&self, games: &Games) -> Result<SessionKey, Error> {
+        let key = games.key();
+
+        if self.get_session(key)?.is_some() {
+            let session_id = self.add_games(games)?;
+            return Ok(SessionKey::build(
+                key.bowler().clone(),
+                key.date(),
+                session_id,
+            ));
+        }
+
+        self.put_session(key, Some(games))?;
+        self.record_edit(
+            Operation::RestoreSession,
+            vec![SessionChange::build(key.clone(), None, Some(games.clone()))],
+        )?;
+
+        Ok(key.clone())
+    }
+
+    // Adds what is about to be removed to the trash, returning the id it was given
+    fn move_to_trash(&self, item: impl FnOnce(u64) -> TrashItem) -> Result<u64, Error> {
+        let trash = self.trash_collection()?;
+        let id = next_seq(&trash)?;
+
+        trash.insert_one(item(id), None)?;
+
+        Ok(id)
+    }
+
+    // Takes out trash items added for a removal that did not go through, nothing was removed
+    // then so nothing is lost when this fails as well
+    fn undo_trash(&self, ids: &[u64]) {
+        for id in ids {
+            let _ = self.delete_trashed(*id);
+        }
+    }
+
+    // Drops trash items holding what `games` brought back, so they can not be restored twice
+    fn forget_restored(&self, games: &Games) -> Result<(), Error> {
+        for item in self.trash()? {
+            if is_restored(&item, games) {
+                self.delete_trashed(item.id())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn trash_collection(&self) -> Result<Collection<TrashItem>, Error> {
+        self.side_collection("trash")
+    }
+
+    fn changes(&self) -> Result<Collection<Change>, Error> {
+        self.side_collection("changes")
+    }
+
+    // Collection kept next to the games, named after them with `suffix`
+    fn side_collection<T>(&self, suffix: &str) -> Result<Collection<T>, Error> {
+        let database = match &self.database {
+            None => return Err(Error::DatabaseNotAssignedError),
+            Some(database) => database,
+        };
+
+        match &self.collection {
+            None => Err(Error::CollectionNotAssignedError),
+            Some(coll) => Ok(database.collection::<T>(&format!("{}_{}", coll.name(), suffix))),
+        }
+    }
+
+    fn record(&self, kind: ChangeKind, sessions: Vec<SessionChange>) -> Result<(), Error> {
+        let changes = self.changes()?;
+        let seq = next_seq(&changes)?;
+
+        changes.insert_one(Change::build(seq, &self.author, kind, sessions), None)?;
+
+        Ok(())
+    }
+
+    fn record_edit(&self, operation: Operation, sessions: Vec<SessionChange>) -> Result<(), Error> {
+        self.record(ChangeKind::Edit(operation), sessions)
+    }
+
+    // Puts every session of `change` back the way it was before, logged as `kind`
+    fn revert(&self, change: &Change, kind: ChangeKind) -> Result<(), Error> {
+        for session in change.sessions() {
+            self.put_session(session.key(), session.before())?;
+
+            if let Some(games) = session.before() {
+                self.forget_restored(games)?;
+            }
+        }
+
+        self.record(
+            kind,
+            change
+                .sessions()
+                .iter()
+                .map(SessionChange::reversed)
+                .collect(),
+        )
+    }
+
+    // Stores `games` under `key` as it is, or removes the session when it is `None`
+    fn put_session(&self, key: &SessionKey, games: Option<&Games>) -> Result<(), Error> {
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll.clone_with_type::<Document>(),
+        };
+
+        let filter = doc! { "_id": to_bson(key).unwrap() };
+
+        match games {
             None => {
-                coll.insert_one(Games::build_with(date, game.clone()), None)?;
+                coll.delete_one(filter, None)?;
             }
-            Some(_) => {
-                coll.update_one(
-                    doc! {
-                        "_id": to_bson(&date).unwrap()
-                    },
-                    doc! {
-                        "$push": doc! {
-                            "games": to_bson(game).unwrap()
-                        }
-                    },
-                    None,
+            Some(games) => {
+                coll.replace_one(
+                    filter,
+                    session_document(games),
+                    ReplaceOptions::builder().upsert(true).build(),
                 )?;
             }
-        };
+        }
 
         Ok(())
     }
 
-    pub fn add_games(&self, games: &Games) -> Result<(), Error> {
+    // Checks every stored document, repairing moves what can not be renumbered to `<collection>_quarantine`
+    pub fn check_integrity(&self, mode: RepairMode) -> Result<IntegrityReport, Error> {
+        let database = match &self.database {
+            None => return Err(Error::DatabaseNotAssignedError),
+            Some(database) => database,
+        };
+
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
+
+        let raw = coll.clone_with_type::<Document>();
+        let quarantine = database.collection::<Document>(&format!("{}_quarantine", coll.name()));
+
+        let stored = raw
+            .find(doc! {}, None)?
+            .collect::<Result<Vec<Document>, _>>()?;
+
+        let mut report = IntegrityReport::default();
+
+        for document in stored {
+            report.add_checked();
+
+            let invalid = match check_document(&document) {
+                None => {
+                    // Sessions stored before there were totals get them, so the server adds them up
+                    if mode == RepairMode::Repair && missing_totals(&document) {
+                        let games = from_document::<Games>(document.clone())
+                            .map_err(|err| Error::CorruptDataError(err.to_string()))?;
+                        raw.replace_one(
+                            doc! { "_id": to_bson(games.key()).unwrap() },
+                            session_document(&games),
+                            None,
+                        )?;
+                    }
+
+                    continue;
+                }
+                Some(invalid) => invalid,
+            };
+
+            report.add_invalid(invalid.clone());
+
+            // Legacy documents are only missing their bowler, migrating keeps their games
+            if mode == RepairMode::Report || invalid.issues().contains(&Issue::Unmigrated) {
+                continue;
+            }
+
+            let filter = doc! { "_id": document.get("_id").cloned().unwrap_or(Bson::Null) };
+
+            match from_document::<Games>(document.clone())
+                .ok()
+                .as_ref()
+                .and_then(renumbered)
+            {
+                Some(games) => {
+                    raw.replace_one(filter, session_document(&games), None)?;
+                    report.add_renumbered(games.key().clone());
+                }
+                None => {
+                    // Replaced so a document quarantined before does not block the move
+                    quarantine.replace_one(
+                        filter.clone(),
+                        document,
+                        ReplaceOptions::builder().upsert(true).build(),
+                    )?;
+                    raw.delete_one(filter, None)?;
+                    report.add_quarantined(invalid.id());
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl GameStore for DatabaseConn {
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
         let filter = doc! {
-            "_id": to_bson(&games.date()).unwrap(),
+            "_id": to_bson(key).unwrap(),
         };
 
         // Check for existing games
-        let result = coll.find_one(filter, None)?;
+        let before = coll.find_one(filter.clone(), None)?;
 
-        match result {
-            Some(_) => return Err(Error::DBConnError),
+        match &before {
             None => {
-                coll.insert_one(games, None)?;
+                coll.clone_with_type::<Document>().insert_one(
+                    session_document(&Games::build_for(key.clone(), vec![game.clone()])),
+                    None,
+                )?;
+            }
+            Some(stored) => {
+                if stored
+                    .games()
+                    .iter()
+                    .any(|g| g.game_num() == game.game_num())
+                {
+                    return Err(Error::DuplicateGameError(key.clone(), game.game_num()));
+                }
+
+                // Inserted in game number order like `Games::add_game`
+                coll.update_one(
+                    filter,
+                    doc! {
+                        "$push": doc! {
+                            "games": doc! {
+                                "$each": [game_document(game, stored.rules())],
+                                "$position": stored.insert_position(game.game_num()) as i64,
+                            }
+                        }
+                    },
+                    None,
+                )?;
             }
         };
 
-        Ok(())
+        self.record_edit(
+            Operation::AddGame,
+            vec![SessionChange::build(
+                key.clone(),
+                before,
+                self.get_session(key)?,
+            )],
+        )
     }
 
-    pub fn get_game(&self, date: Date, game_num: u8) -> Result<Option<Game>, Error> {
+    fn add_games(&self, games: &Games) -> Result<u8, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
-        let filter = doc! {
-            "_id": to_bson(&date).unwrap(),
-        };
+        let session_id = self
+            .get_games(games.bowler(), games.date())?
+            .iter()
+            .map(Games::session_id)
+            .max()
+            .unwrap_or(0)
+            + 1;
 
-        let result = coll.find_one(filter, None)?;
+        let key = SessionKey::build(games.bowler().clone(), games.date(), session_id);
 
-        let games = match result {
-            None => return Ok(None),
-            Some(games) => games,
-        };
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(key, game_num));
+        }
 
-        let game = games
-            .games()
-            .iter()
-            .find(|g| g.game_num() == game_num)
-            .cloned();
+        let mut games = games.clone();
+        games.set_key(key);
+
+        coll.clone_with_type::<Document>()
+            .insert_one(session_document(&games), None)?;
 
-        Ok(game)
+        self.record_edit(
+            Operation::AddGames,
+            vec![SessionChange::build(games.key().clone(), None, Some(games))],
+        )?;
+
+        Ok(session_id)
     }
 
-    pub fn get_games(&self, date: Date) -> Result<Option<Games>, Error> {
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
         let filter = doc! {
-            "_id": to_bson(&date).unwrap(),
+            "_id": to_bson(key).unwrap(),
         };
 
         Ok(coll.find_one(filter, None)?)
     }
 
-    pub fn num_games(&self, date: Date) -> Result<u8, Error> {
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
-        let filter = doc! {
-            "_id": to_bson(&date).unwrap(),
+        let mut sessions = coll
+            .find(day_filter(bowler, date), None)?
+            .collect::<Result<Vec<Games>, _>>()?;
+        sessions.sort_by_key(Games::session_id);
+
+        Ok(sessions)
+    }
+
+    fn query(&self, query: &GameQuery) -> Result<GamesIter<'_>, Error> {
+        if self.database.is_none() {
+            return Err(Error::DatabaseNotAssignedError);
         };
 
-        let result = coll.find_one(filter, None)?;
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
 
-        Ok(match result {
-            None => 0,
-            Some(games) => games.games().len() as u8,
-        })
+        // A limit of 0 means no limit to MongoDB
+        if query.limit() == Some(0) {
+            return Ok(Box::new(std::iter::empty()));
+        }
+
+        let filter = query_filter(query);
+
+        let direction = match query.order() {
+            SortOrder::Ascending => 1,
+            SortOrder::Descending => -1,
+        };
+
+        let mut options = FindOptions::builder()
+            .sort(doc! {
+                "_id.date": direction,
+                "_id.bowler": direction,
+                "_id.session": direction,
+            })
+            .build();
+
+        // Scores are not stored, so with a minimum score the page is only known after filtering
+        let paged = query.min_score().is_none();
+
+        if paged {
+            options.skip = Some(query.skip() as u64);
+            options.limit = query.limit().map(|limit| limit as i64);
+        }
+
+        let matcher = query.clone();
+        let sessions = coll
+            .find(filter, options)?
+            .map(|games| games.map_err(Error::from))
+            .filter(move |games| games.as_ref().map_or(true, |g| matcher.matches(g)));
+
+        if paged {
+            return Ok(Box::new(sessions));
+        }
+
+        Ok(Box::new(query.page(sessions)))
     }
 
-    pub fn modify_game(&self, date: Date, game: &Game) -> Result<(), Error> {
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
+        // Totals are scored with the rules of the session the game is in
+        let before = match self.get_session(key)? {
+            None => return Err(Error::GameNotFoundError(key.clone(), game.game_num())),
+            Some(stored) => stored,
+        };
+
         let query = doc! {
-            "_id": to_bson(&date).unwrap(),
+            "_id": to_bson(key).unwrap(),
             "games.game_num": to_bson(&game.game_num()).unwrap(),
         };
 
         let update = doc! {
             "$set": doc! {
-                "games.$.frames": to_bson(&game.frames()).unwrap(),
+                "games.$": game_document(game, before.rules()),
             },
         };
 
         if coll.update_one(query, update, None)?.matched_count == 0 {
-            return Err(Error::DBConnError);
+            return Err(Error::GameNotFoundError(key.clone(), game.game_num()));
         };
 
-        Ok(())
+        self.record_edit(
+            Operation::ModifyGame,
+            vec![SessionChange::build(
+                key.clone(),
+                Some(before),
+                self.get_session(key)?,
+            )],
+        )
     }
 
-    pub fn modify_games(&self, games: &Games) -> Result<(), Error> {
+    fn modify_games(&self, games: &Games) -> Result<(), Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(games.key().clone(), game_num));
+        }
+
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
         let filter = doc! {
-            "_id": to_bson(&games.date()).unwrap(),
+            "_id": to_bson(games.key()).unwrap(),
         };
 
-        if coll
-            .find_one_and_replace(filter, games.clone(), None)?
-            .is_none()
-        {
-            return Err(Error::DBConnError);
-        }
+        let before = match coll.clone_with_type::<Document>().find_one_and_replace(
+            filter,
+            session_document(games),
+            None,
+        )? {
+            None => return Err(Error::SessionNotFoundError(games.key().clone())),
+            Some(before) => from_document::<Games>(before).ok(),
+        };
 
-        Ok(())
+        self.record_edit(
+            Operation::ModifyGames,
+            vec![SessionChange::build(
+                games.key().clone(),
+                before,
+                Some(games.clone()),
+            )],
+        )
     }
 
-    pub fn remove_game(&self, date: Date, game_num: u8) -> Result<Option<Game>, Error> {
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
-        if game_num == 0 || game_num > self.num_games(date)? {
-            return Ok(None);
-        };
+        let before = self.get_session(key)?;
 
-        let game = self.get_game(date, game_num)?.unwrap();
+        let game = match before
+            .as_ref()
+            .and_then(|s| s.games().iter().find(|g| g.game_num() == game_num).cloned())
+        {
+            None => return Ok(None),
+            Some(game) => game,
+        };
 
         let query = doc! {
-            "_id": to_bson(&date).unwrap(),
+            "_id": to_bson(key).unwrap(),
         };
 
         let update = doc! {
@@ -354,46 +974,538 @@ impl DatabaseConn {
             },
         };
 
-        if coll.update_one(query, update, None)?.matched_count == 0 {
-            return Err(Error::DBConnError);
-        }
+        // The game goes in the trash first, so it is never gone from both
+        let trashed = match &before {
+            None => return Ok(None),
+            Some(session) => {
+                self.move_to_trash(|id| TrashItem::build_game(id, session, &game, self.retention))?
+            }
+        };
+
+        match coll.update_one(query, update, None) {
+            Err(err) => {
+                self.undo_trash(&[trashed]);
+                return Err(err.into());
+            }
+            Ok(result) if result.matched_count == 0 => {
+                self.undo_trash(&[trashed]);
+                return Err(Error::SessionNotFoundError(key.clone()));
+            }
+            Ok(_) => {}
+        };
+
+        self.record_edit(
+            Operation::RemoveGame,
+            vec![SessionChange::build(
+                key.clone(),
+                before,
+                self.get_session(key)?,
+            )],
+        )?;
 
         Ok(Some(game))
     }
 
-    pub fn remove_games(&self, date: Date) -> Result<Option<Games>, Error> {
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
+        let games = match self.get_session(key)? {
+            None => return Ok(None),
+            Some(games) => games,
+        };
+
         let filter = doc! {
-            "_id": to_bson(&date).unwrap(),
+            "_id": to_bson(key).unwrap(),
         };
 
-        Ok(coll.find_one_and_delete(filter, None)?)
+        let trashed =
+            self.move_to_trash(|id| TrashItem::build_session(id, &games, self.retention))?;
+
+        match coll.delete_one(filter, None) {
+            Err(err) => {
+                self.undo_trash(&[trashed]);
+                return Err(err.into());
+            }
+            Ok(result) if result.deleted_count == 0 => {
+                self.undo_trash(&[trashed]);
+                return Ok(None);
+            }
+            Ok(_) => {}
+        };
+
+        self.record_edit(
+            Operation::RemoveSession,
+            vec![SessionChange::build(key.clone(), Some(games.clone()), None)],
+        )?;
+
+        Ok(Some(games))
     }
 
-    pub fn drop_all(&self) -> Result<(), Error> {
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
         if self.database.is_none() {
-            return Err(Error::DBConnError);
+            return Err(Error::DatabaseNotAssignedError);
         };
 
         let coll = match &self.collection {
-            None => return Err(Error::DBConnError),
+            None => return Err(Error::CollectionNotAssignedError),
             Some(coll) => coll,
         };
 
+        let sessions = self.get_games(bowler, date)?;
+
+        let mut trashed = Vec::new();
+        for games in &sessions {
+            match self.move_to_trash(|id| TrashItem::build_session(id, games, self.retention)) {
+                Err(err) => {
+                    self.undo_trash(&trashed);
+                    return Err(err);
+                }
+                Ok(id) => trashed.push(id),
+            };
+        }
+
+        if let Err(err) = coll.delete_many(day_filter(bowler, date), None) {
+            self.undo_trash(&trashed);
+            return Err(err.into());
+        }
+
+        if !sessions.is_empty() {
+            self.record_edit(Operation::RemoveGames, removed_changes(&sessions))?;
+        }
+
+        Ok(sessions)
+    }
+
+    // Kept in `<collection>_trash`
+    fn trash(&self) -> Result<Vec<TrashItem>, Error> {
+        let options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+
+        Ok(self
+            .trash_collection()?
+            .find(doc! {}, options)?
+            .collect::<Result<Vec<TrashItem>, _>>()?)
+    }
+
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error> {
+        Ok(self
+            .trash_collection()?
+            .delete_one(doc! { "_id": to_bson(&id).unwrap() }, None)?
+            .deleted_count
+            > 0)
+    }
+
+    // A removed session goes back through `put_back`, so restoring it can be undone
+    fn restore_trashed(&self, id: u64) -> Result<Option<SessionKey>, Error> {
+        let item = match self
+            .trash_collection()?
+            .find_one(doc! { "_id": to_bson(&id).unwrap() }, None)?
+        {
+            None => return Ok(None),
+            Some(item) => item,
+        };
+
+        let key = match (item.game(), self.get_session(item.key())?) {
+            (Some(game), Some(stored)) => {
+                self.add_game(item.key(), &numbered_for(game, &stored))?;
+                item.key().clone()
+            }
+            _ => self.put_back(item.games())?,
+        };
+
+        self.delete_trashed(id)?;
+
+        Ok(Some(key))
+    }
+
+    fn purge_trash(&self) -> Result<u64, Error> {
+        let expired = doc! { "expires_at": doc! { "$lte": DateTime::now() } };
+
+        Ok(self
+            .trash_collection()?
+            .delete_many(expired, None)?
+            .deleted_count)
+    }
+
+    // Added up on the server from the totals stored with each game. Sessions stored before there
+    // were totals are added up here instead until `check_integrity` repairs them
+    fn totals(&self, query: &GameQuery, include_no_tap: bool) -> Result<GameTotals, Error> {
+        if self.database.is_none() {
+            return Err(Error::DatabaseNotAssignedError);
+        };
+
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
+
+        // A limit of 0 means no limit to MongoDB
+        if query.limit() == Some(0) {
+            return Ok(GameTotals::build());
+        }
+
+        let mut filter = migrated_filter(query);
+
+        // Any game of the session scoring enough, like `GameQuery::matches`
+        if let Some(min_score) = query.min_score() {
+            filter.insert("games.totals.score", doc! { "$gte": min_score as i64 });
+        }
+
+        let mut missing = migrated_filter(query);
+        missing.insert(
+            "games",
+            doc! { "$elemMatch": doc! { "totals": doc! { "$exists": false } } },
+        );
+
+        if coll
+            .clone_with_type::<Document>()
+            .find_one(missing, None)?
+            .is_some()
+        {
+            return fold_totals(self, query, include_no_tap);
+        }
+
+        let direction = match query.order() {
+            SortOrder::Ascending => 1,
+            SortOrder::Descending => -1,
+        };
+
+        // Sessions are paged in the order `query` hands them out before their games are unwound
+        let mut pipeline = vec![
+            doc! { "$match": filter },
+            doc! { "$sort": doc! {
+                "_id.date": direction,
+                "_id.bowler": direction,
+                "_id.session": direction,
+            } },
+            doc! { "$skip": query.skip() as i64 },
+        ];
+
+        if let Some(limit) = query.limit() {
+            pipeline.push(doc! { "$limit": limit as i64 });
+        }
+
+        let counted = match include_no_tap {
+            true => doc! {},
+            false => doc! { "games.totals.no_tap": false },
+        };
+
+        let mut group = doc! { "_id": Bson::Null };
+
+        for field in to_document(&GameTotals::build()).unwrap().keys() {
+            group.insert(field, doc! { "$sum": format!("$games.totals.{}", field) });
+        }
+
+        pipeline.extend([
+            doc! { "$unwind": "$games" },
+            doc! { "$match": counted },
+            doc! { "$group": group },
+        ]);
+
+        match coll.aggregate(pipeline, None)?.next() {
+            None => Ok(GameTotals::build()),
+            Some(totals) => from_document(totals?).map_err(|_| {
+                Error::CorruptDataError(String::from("stored totals are not numbers"))
+            }),
+        }
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error> {
+        if self.database.is_none() {
+            return Err(Error::DatabaseNotAssignedError);
+        };
+
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
+
+        let names = coll.distinct("_id.bowler", None, None)?;
+
+        Ok(names
+            .iter()
+            .filter_map(Bson::as_str)
+            .map(Bowler::build)
+            .collect())
+    }
+
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error> {
+        if self.database.is_none() {
+            return Err(Error::DatabaseNotAssignedError);
+        };
+
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
+
+        let days = coll.distinct("_id.date", doc! { "_id.bowler": bowler.name() }, None)?;
+
+        let mut dates = days
+            .into_iter()
+            .map(|day| {
+                from_bson::<Date>(day)
+                    .map_err(|_| Error::CorruptDataError(String::from("stored date is not a date")))
+            })
+            .collect::<Result<Vec<Date>, _>>()?;
+        dates.sort_by_key(|d| (d.year(), d.month(), d.day()));
+
+        Ok(dates)
+    }
+
+    fn drop_all(&self) -> Result<(), Error> {
+        if self.database.is_none() {
+            return Err(Error::DatabaseNotAssignedError);
+        };
+
+        let coll = match &self.collection {
+            None => return Err(Error::CollectionNotAssignedError),
+            Some(coll) => coll,
+        };
+
+        let sessions = coll
+            .find(doc! {}, None)?
+            .collect::<Result<Vec<Games>, _>>()?;
+
+        // Dropping everything is deliberate, so it skips the trash, the change log can still undo it
         coll.delete_many(doc! {}, None)?;
 
+        if !sessions.is_empty() {
+            self.record_edit(Operation::DropAll, removed_changes(&sessions))?;
+        }
+
         Ok(())
     }
 
-    pub fn validate(&self) -> bool {
-        todo!()
+    // Documents that do not decode are reported too, not only the games of those that do
+    fn integrity_report(&self) -> Result<IntegrityReport, Error> {
+        self.check_integrity(RepairMode::Report)
     }
 }
+
+// Runs `attempt` until it succeeds, retrying failures that may pass with the configured backoff
+fn retry<T>(
+    options: ConnectOptions,
+    step: ConnectStep,
+    attempt: impl Fn() -> mongodb::error::Result<T>,
+) -> Result<T, Error> {
+    let mut delay = options.backoff();
+    let mut tries = 0;
+
+    loop {
+        let err = match attempt() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+
+        let transient = matches!(
+            *err.kind,
+            ErrorKind::ServerSelection { .. }
+                | ErrorKind::Io(_)
+                | ErrorKind::ConnectionPoolCleared { .. }
+                | ErrorKind::DnsResolve { .. }
+        );
+
+        if !transient || tries >= options.retries() {
+            // A rejected login shows up on whichever step first talks to the server
+            let step = match *err.kind {
+                ErrorKind::Authentication { .. } => ConnectStep::Authenticate,
+                _ => step,
+            };
+
+            return Err(Error::ConnectionError(step, err));
+        }
+
+        thread::sleep(delay);
+        delay *= 2;
+        tries += 1;
+    }
+}
+
+// Queries go by bowler and day, or by day alone
+fn create_indexes(collection: &Collection<Games>) -> mongodb::error::Result<()> {
+    collection.create_indexes(
+        [
+            IndexModel::builder()
+                .keys(doc! { "_id.bowler": 1, "_id.date": 1 })
+                .build(),
+            IndexModel::builder().keys(doc! { "_id.date": 1 }).build(),
+        ],
+        None,
+    )?;
+
+    Ok(())
+}
+
+// Matches the bowler, days and session label of `query`, the rest is left to `GameQuery::matches`
+fn query_filter(query: &GameQuery) -> Document {
+    let mut filter = doc! {};
+
+    if let Some(bowler) = query.bowler() {
+        filter.insert("_id.bowler", bowler.name());
+    }
+
+    // Dates are stored as year, month and day, so they compare as documents in date order
+    let mut range = doc! {};
+
+    if let Some(from) = query.from() {
+        range.insert("$gte", to_bson(&from).unwrap());
+    }
+
+    if let Some(to) = query.to() {
+        range.insert("$lte", to_bson(&to).unwrap());
+    }
+
+    if !range.is_empty() {
+        filter.insert("_id.date", range);
+    }
+
+    if let Some(label) = query.label() {
+        filter.insert("session.label", to_bson(label).unwrap());
+    }
+
+    filter
+}
+
+// Whether a game of a stored session has no totals yet
+fn missing_totals(document: &Document) -> bool {
+    document.get_array("games").is_ok_and(|games| {
+        games
+            .iter()
+            .any(|game| game.as_document().is_none_or(|g| !g.contains_key("totals")))
+    })
+}
+
+// `query_filter` leaving out the documents from before there were bowlers, which are left to `migrate`
+fn migrated_filter(query: &GameQuery) -> Document {
+    let mut filter = query_filter(query);
+
+    if query.bowler().is_none() {
+        filter.insert("_id.bowler", doc! { "$exists": true });
+    }
+
+    filter
+}
+
+// A game as stored, with the totals statistics are added up from
+pub(crate) fn game_document(game: &Game, rules: RuleSet) -> Document {
+    let mut document = to_document(game).unwrap();
+    let mut totals = to_document(&GameTotals::build_from_game(game, rules)).unwrap();
+    totals.insert("no_tap", game.is_no_tap());
+    document.insert("totals", totals);
+
+    document
+}
+
+pub(crate) fn session_document(games: &Games) -> Document {
+    let mut document = to_document(games).unwrap();
+    document.insert(
+        "games",
+        games
+            .games()
+            .iter()
+            .map(|g| Bson::Document(game_document(g, games.rules())))
+            .collect::<Vec<Bson>>(),
+    );
+
+    document
+}
+
+// Documents that do not decode are stored as they are, so they can still be found and repaired
+fn with_totals(document: Document) -> Document {
+    match from_document::<Games>(document.clone()) {
+        Ok(games) => session_document(&games),
+        Err(_) => document,
+    }
+}
+
+// `uri` with the password of its user info replaced by `***`
+pub(crate) fn redacted_uri(uri: &str) -> String {
+    let (scheme, rest) = match uri.split_once("://") {
+        None => return uri.to_string(),
+        Some(parts) => parts,
+    };
+
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    let user_info = match authority.rfind('@') {
+        None => return uri.to_string(),
+        Some(at) => &authority[..at],
+    };
+
+    match user_info.split_once(':') {
+        None => uri.to_string(),
+        Some((user, _)) => format!("{scheme}://{user}:***{}", &rest[user_info.len()..]),
+    }
+}
+
+// Number after the highest `_id` of a collection numbered from 1
+fn next_seq<T>(collection: &Collection<T>) -> Result<u64, Error> {
+    let last = collection.clone_with_type::<Document>().find_one(
+        doc! {},
+        FindOneOptions::builder().sort(doc! { "_id": -1 }).build(),
+    )?;
+
+    match last.and_then(|document| document.get("_id").cloned()) {
+        None => Ok(1),
+        Some(id) => from_bson::<u64>(id)
+            .map(|seq| seq + 1)
+            .map_err(|_| Error::CorruptDataError(String::from("_id is not a number"))),
+    }
+}
+
+// Sessions that were removed, as they were before
+fn removed_changes(sessions: &[Games]) -> Vec<SessionChange> {
+    sessions
+        .iter()
+        .map(|games| SessionChange::build(games.key().clone(), Some(games.clone()), None))
+        .collect()
+}
+
+// Filter on the bowler and day a document is stored under
+fn day_filter(bowler: &Bowler, date: Date) -> Document {
+    doc! {
+        "_id.bowler": bowler.name(),
+        "_id.date": to_bson(&date).unwrap(),
+    }
+}
+
+// Decodes a stored document and checks its games, `None` when it is a valid session
+pub(crate) fn check_document(document: &Document) -> Option<InvalidSession> {
+    let id = document
+        .get("_id")
+        .map_or_else(|| String::from("document without an _id"), Bson::to_string);
+
+    let legacy = document
+        .get("_id")
+        .is_some_and(|id| from_bson::<Date>(id.clone()).is_ok());
+
+    let (key, issues) = match from_document::<Games>(document.clone()) {
+        Err(_) if legacy => (None, vec![Issue::Unmigrated]),
+        Err(err) => (None, vec![Issue::Undecodable(err.to_string())]),
+        Ok(games) => (Some(games.key().clone()), check_games(&games)),
+    };
+
+    if issues.is_empty() {
+        return None;
+    }
+
+    Some(InvalidSession::build(&id, key, issues))
+}
+
+// Rekeys a document stored under its date alone as the first session of `bowler` on that date
+pub(crate) fn migrate_document(mut old: Document, bowler: &Bowler) -> Result<Document, Error> {
+    let id = old.get("_id").cloned().unwrap_or(Bson::Null);
+    let date = from_bson::<Date>(id.clone())
+        .map_err(|_| Error::CorruptDataError(format!("_id {} is not a date", id)))?;
+
+    let key = SessionKey::build(bowler.clone(), date, 1);
+    old.insert("_id", to_bson(&key).unwrap());
+
+    Ok(old)
+}
@@ -0,0 +1,148 @@
+use crate::backend::core::types::{Bowler, Date, Games, SessionLabel};
+use crate::error::CoreError as Error;
+
+// Sessions handed out one at a time, so a query does not load every match at once
+pub type GamesIter<'a> = Box<dyn Iterator<Item = Result<Games, Error>> + 'a>;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SortOrder {
+    // Earliest day first
+    #[default]
+    Ascending,
+    // Latest day first
+    Descending,
+}
+
+// Which sessions to fetch, everything that is not set matches every session
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GameQuery {
+    bowler: Option<Bowler>,
+    from: Option<Date>,
+    to: Option<Date>,
+    label: Option<SessionLabel>,
+    min_score: Option<u16>,
+    order: SortOrder,
+    skip: usize,
+    limit: Option<usize>,
+}
+
+impl GameQuery {
+    // Constructor
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    // Sessions of `bowler` between `from` and `to`, both included
+    pub fn build_between(bowler: &Bowler, from: Date, to: Date) -> Self {
+        Self {
+            bowler: Some(bowler.clone()),
+            from: Some(from),
+            to: Some(to),
+            ..Self::default()
+        }
+    }
+
+    // Getter
+    pub fn bowler(&self) -> Option<&Bowler> {
+        self.bowler.as_ref()
+    }
+
+    pub fn from(&self) -> Option<Date> {
+        self.from
+    }
+
+    pub fn to(&self) -> Option<Date> {
+        self.to
+    }
+
+    pub fn label(&self) -> Option<&SessionLabel> {
+        self.label.as_ref()
+    }
+
+    // Sessions need at least one game scoring this much under their rules
+    pub fn min_score(&self) -> Option<u16> {
+        self.min_score
+    }
+
+    pub fn order(&self) -> SortOrder {
+        self.order
+    }
+
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    // Setter
+    pub fn set_bowler(&mut self, bowler: Option<&Bowler>) {
+        self.bowler = bowler.cloned();
+    }
+
+    pub fn set_from(&mut self, from: Option<Date>) {
+        self.from = from;
+    }
+
+    pub fn set_to(&mut self, to: Option<Date>) {
+        self.to = to;
+    }
+
+    pub fn set_label(&mut self, label: Option<SessionLabel>) {
+        self.label = label;
+    }
+
+    pub fn set_min_score(&mut self, min_score: Option<u16>) {
+        self.min_score = min_score;
+    }
+
+    pub fn set_order(&mut self, order: SortOrder) {
+        self.order = order;
+    }
+
+    // Page `page` counted from 0, `size` sessions to a page
+    pub fn set_page(&mut self, page: usize, size: usize) {
+        self.skip = page * size;
+        self.limit = Some(size);
+    }
+
+    pub fn set_skip(&mut self, skip: usize) {
+        self.skip = skip;
+    }
+
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    // Method
+    pub fn matches_day(&self, bowler: &Bowler, date: Date) -> bool {
+        self.bowler.as_ref().is_none_or(|b| b == bowler)
+            && self.from.is_none_or(|from| date >= from)
+            && self.to.is_none_or(|to| date <= to)
+    }
+
+    pub fn matches(&self, games: &Games) -> bool {
+        self.matches_day(games.bowler(), games.date())
+            && self
+                .label
+                .as_ref()
+                .is_none_or(|label| games.session().label() == label)
+            && self.min_score.is_none_or(|min| {
+                games
+                    .games()
+                    .iter()
+                    .any(|g| g.score_with(games.rules()) >= min)
+            })
+    }
+
+    // Only the page asked for out of `sessions`
+    pub fn page<'a>(
+        &self,
+        sessions: impl Iterator<Item = Result<Games, Error>> + 'a,
+    ) -> impl Iterator<Item = Result<Games, Error>> + 'a {
+        sessions
+            .skip(self.skip)
+            .take(self.limit.unwrap_or(usize::MAX))
+    }
+}
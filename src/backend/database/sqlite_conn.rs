@@ -0,0 +1,799 @@
+use std::{io, path::Path, time::Duration};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::db_conn::{date_key, duplicate_game, parse_date_key, GameStore};
+use super::trash::{TrashItem, DEFAULT_RETENTION};
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{
+    Bowler, Date, Frame, Game, Games, Marker, Pins, Session, SessionKey, SessionLabel, Time,
+};
+use crate::backend::core::variant::Variant;
+use crate::error::CoreError as Error;
+
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+
+    CREATE TABLE IF NOT EXISTS sessions (
+        bowler TEXT NOT NULL,
+        date TEXT NOT NULL,
+        session INTEGER NOT NULL,
+        start TEXT,
+        label TEXT NOT NULL,
+        other_label TEXT,
+        location TEXT,
+        rules TEXT NOT NULL,
+        PRIMARY KEY (bowler, date, session)
+    );
+
+    CREATE TABLE IF NOT EXISTS games (
+        bowler TEXT NOT NULL,
+        date TEXT NOT NULL,
+        session INTEGER NOT NULL,
+        game_num INTEGER NOT NULL,
+        variant TEXT NOT NULL,
+        no_tap INTEGER,
+        PRIMARY KEY (bowler, date, session, game_num),
+        FOREIGN KEY (bowler, date, session)
+            REFERENCES sessions (bowler, date, session) ON DELETE CASCADE
+    );
+
+    CREATE TABLE IF NOT EXISTS frames (
+        bowler TEXT NOT NULL,
+        date TEXT NOT NULL,
+        session INTEGER NOT NULL,
+        game_num INTEGER NOT NULL,
+        frame_no INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        first INTEGER,
+        second INTEGER,
+        third INTEGER,
+        PRIMARY KEY (bowler, date, session, game_num, frame_no),
+        FOREIGN KEY (bowler, date, session, game_num)
+            REFERENCES games (bowler, date, session, game_num) ON DELETE CASCADE
+    );
+
+    CREATE TABLE IF NOT EXISTS markers (
+        bowler TEXT NOT NULL,
+        date TEXT NOT NULL,
+        session INTEGER NOT NULL,
+        game_num INTEGER NOT NULL,
+        frame_no INTEGER NOT NULL,
+        ball INTEGER NOT NULL,
+        marker TEXT NOT NULL,
+        PRIMARY KEY (bowler, date, session, game_num, frame_no, ball),
+        FOREIGN KEY (bowler, date, session, game_num)
+            REFERENCES games (bowler, date, session, game_num) ON DELETE CASCADE
+    );
+
+    CREATE TABLE IF NOT EXISTS deadwood (
+        bowler TEXT NOT NULL,
+        date TEXT NOT NULL,
+        session INTEGER NOT NULL,
+        game_num INTEGER NOT NULL,
+        frame_no INTEGER NOT NULL,
+        ball INTEGER NOT NULL,
+        pins INTEGER NOT NULL,
+        PRIMARY KEY (bowler, date, session, game_num, frame_no, ball),
+        FOREIGN KEY (bowler, date, session, game_num)
+            REFERENCES games (bowler, date, session, game_num) ON DELETE CASCADE
+    );
+
+    CREATE TABLE IF NOT EXISTS trash (
+        id INTEGER PRIMARY KEY,
+        item TEXT NOT NULL
+    );
+";
+
+// Games kept in a single local SQLite file, one table each for sessions, games, frames, markers and
+// deadwood
+pub struct SqliteConn {
+    conn: Connection,
+    // How long removed games stay in the trash
+    retention: Duration,
+}
+
+impl SqliteConn {
+    // Constructor
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::build_from(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::build_from(Connection::open_in_memory()?)
+    }
+
+    fn build_from(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(SqliteConn {
+            conn,
+            retention: DEFAULT_RETENTION,
+        })
+    }
+
+    // Getter
+    pub fn trash_retention(&self) -> Duration {
+        self.retention
+    }
+
+    // Setter
+    // Applies to what is removed from now on
+    pub fn set_trash_retention(&mut self, retention: Duration) {
+        self.retention = retention;
+    }
+
+    // Method
+    fn session_exists(&self, key: &SessionKey) -> Result<bool, Error> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT 1 FROM sessions WHERE bowler = ?1 AND date = ?2 AND session = ?3",
+                params![key.bowler().name(), date_key(key.date()), key.session_id()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    fn game_exists(&self, key: &SessionKey, game_num: u8) -> Result<bool, Error> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT 1 FROM games
+                 WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+                params![
+                    key.bowler().name(),
+                    date_key(key.date()),
+                    key.session_id(),
+                    game_num
+                ],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    fn session_ids(&self, bowler: &Bowler, date: Date) -> Result<Vec<u8>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT session FROM sessions WHERE bowler = ?1 AND date = ?2 ORDER BY session",
+        )?;
+
+        let ids = stmt
+            .query_map(params![bowler.name(), date_key(date)], |row| row.get(0))?
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        Ok(ids)
+    }
+
+    fn read_game(&self, key: &SessionKey, game_num: u8, variant: Variant) -> Result<Game, Error> {
+        let key_params = params![
+            key.bowler().name(),
+            date_key(key.date()),
+            key.session_id(),
+            game_num
+        ];
+
+        let mut game = Game::build_with_variant(game_num, variant);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT frame_no, kind, first, second, third FROM frames
+             WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+        )?;
+
+        let frames = stmt
+            .query_map(key_params, |row| {
+                Ok((
+                    row.get::<_, u8>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<u16>>(2)?,
+                    row.get::<_, Option<u16>>(3)?,
+                    row.get::<_, Option<u16>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (frame_no, kind, first, second, third) in frames {
+            let slot = game
+                .frames_mut()
+                .get_mut(frame_no.wrapping_sub(1) as usize)
+                .ok_or_else(|| corrupt("frame number", &frame_no.to_string()))?;
+
+            *slot =
+                decode_frame(&kind, first, second, third).ok_or_else(|| corrupt("frame", &kind))?;
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT frame_no, ball, marker FROM markers
+             WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+        )?;
+
+        let markers = stmt
+            .query_map(key_params, |row| {
+                Ok((
+                    row.get::<_, u8>(0)?,
+                    row.get::<_, u8>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (frame_no, ball, marker) in markers {
+            let marker = decode_marker(&marker).ok_or_else(|| corrupt("marker", &marker))?;
+            game.set_marker(frame_no, ball, Some(marker));
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT frame_no, ball, pins FROM deadwood
+             WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+        )?;
+
+        let deadwood = stmt
+            .query_map(key_params, |row| {
+                Ok((
+                    row.get::<_, u8>(0)?,
+                    row.get::<_, u8>(1)?,
+                    row.get::<_, u8>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (frame_no, ball, pins) in deadwood {
+            game.set_deadwood(frame_no, ball, pins);
+        }
+
+        Ok(game)
+    }
+
+    fn read_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        let key_params = params![key.bowler().name(), date_key(key.date()), key.session_id()];
+
+        let row = self
+            .conn
+            .query_row(
+                "SELECT start, label, other_label, location, rules FROM sessions
+                 WHERE bowler = ?1 AND date = ?2 AND session = ?3",
+                key_params,
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        let (start, label, other_label, location, rules) = match row {
+            None => return Ok(None),
+            Some(row) => row,
+        };
+
+        let mut session = Session::default();
+        session.set_start(match start {
+            None => None,
+            Some(start) => Some(decode_time(&start).ok_or_else(|| corrupt("start time", &start))?),
+        });
+        session
+            .set_label(decode_label(&label, other_label).ok_or_else(|| corrupt("label", &label))?);
+        session.set_location(location.as_deref());
+
+        let mut stmt = self.conn.prepare(
+            "SELECT game_num, variant, no_tap FROM games
+             WHERE bowler = ?1 AND date = ?2 AND session = ?3 ORDER BY game_num",
+        )?;
+
+        let rows = stmt
+            .query_map(key_params, |row| {
+                Ok((
+                    row.get::<_, u8>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<u8>>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut games = Vec::new();
+
+        for (game_num, variant, no_tap) in rows {
+            let variant = decode_variant(&variant).ok_or_else(|| corrupt("variant", &variant))?;
+
+            let mut game = self.read_game(key, game_num, variant)?;
+            game.set_no_tap(no_tap);
+
+            games.push(game);
+        }
+
+        let mut session_games = Games::build_for(key.clone(), games);
+        session_games.set_session(session);
+        session_games.set_rules(decode_rules(&rules).ok_or_else(|| corrupt("rules", &rules))?);
+
+        Ok(Some(session_games))
+    }
+}
+
+impl GameStore for SqliteConn {
+    fn add_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        if self.game_exists(key, game.game_num())? {
+            return Err(Error::DuplicateGameError(key.clone(), game.game_num()));
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        if !self.session_exists(key)? {
+            insert_session(&tx, &Games::build_for(key.clone(), Vec::new()))?;
+        }
+
+        insert_game(&tx, key, game)?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn add_games(&self, games: &Games) -> Result<u8, Error> {
+        let session_id = self
+            .session_ids(games.bowler(), games.date())?
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        let key = SessionKey::build(games.bowler().clone(), games.date(), session_id);
+
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(key, game_num));
+        }
+
+        let mut games = games.clone();
+        games.set_key(key);
+
+        let tx = self.conn.unchecked_transaction()?;
+        insert_session(&tx, &games)?;
+        tx.commit()?;
+
+        Ok(session_id)
+    }
+
+    fn get_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        self.read_session(key)
+    }
+
+    fn get_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        let mut sessions = Vec::new();
+
+        for session_id in self.session_ids(bowler, date)? {
+            let key = SessionKey::build(bowler.clone(), date, session_id);
+
+            if let Some(games) = self.read_session(&key)? {
+                sessions.push(games);
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    fn modify_game(&self, key: &SessionKey, game: &Game) -> Result<(), Error> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        let removed = tx.execute(
+            "DELETE FROM games WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+            params![
+                key.bowler().name(),
+                date_key(key.date()),
+                key.session_id(),
+                game.game_num()
+            ],
+        )?;
+
+        if removed == 0 {
+            return Err(Error::GameNotFoundError(key.clone(), game.game_num()));
+        }
+
+        insert_game(&tx, key, game)?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn modify_games(&self, games: &Games) -> Result<(), Error> {
+        if let Some(game_num) = duplicate_game(games) {
+            return Err(Error::DuplicateGameError(games.key().clone(), game_num));
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        if delete_session(&tx, games.key())? == 0 {
+            return Err(Error::SessionNotFoundError(games.key().clone()));
+        }
+
+        insert_session(&tx, games)?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn remove_game(&self, key: &SessionKey, game_num: u8) -> Result<Option<Game>, Error> {
+        let session = match self.read_session(key)? {
+            None => return Ok(None),
+            Some(session) => session,
+        };
+
+        let game = match session.games().iter().find(|g| g.game_num() == game_num) {
+            None => return Ok(None),
+            Some(game) => game.clone(),
+        };
+
+        // The trash row and the delete go in together or not at all
+        let tx = self.conn.unchecked_transaction()?;
+
+        let id = next_trash_id(&tx)?;
+        insert_trashed(
+            &tx,
+            &TrashItem::build_game(id, &session, &game, self.retention),
+        )?;
+
+        tx.execute(
+            "DELETE FROM games WHERE bowler = ?1 AND date = ?2 AND session = ?3 AND game_num = ?4",
+            params![
+                key.bowler().name(),
+                date_key(key.date()),
+                key.session_id(),
+                game_num
+            ],
+        )?;
+
+        tx.commit()?;
+
+        Ok(Some(game))
+    }
+
+    fn remove_session(&self, key: &SessionKey) -> Result<Option<Games>, Error> {
+        let session = match self.read_session(key)? {
+            None => return Ok(None),
+            Some(session) => session,
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        let id = next_trash_id(&tx)?;
+        insert_trashed(&tx, &TrashItem::build_session(id, &session, self.retention))?;
+        delete_session(&tx, key)?;
+
+        tx.commit()?;
+
+        Ok(Some(session))
+    }
+
+    fn remove_games(&self, bowler: &Bowler, date: Date) -> Result<Vec<Games>, Error> {
+        let sessions = self.get_games(bowler, date)?;
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        let first = next_trash_id(&tx)?;
+        for (id, games) in (first..).zip(&sessions) {
+            insert_trashed(&tx, &TrashItem::build_session(id, games, self.retention))?;
+        }
+
+        tx.execute(
+            "DELETE FROM sessions WHERE bowler = ?1 AND date = ?2",
+            params![bowler.name(), date_key(date)],
+        )?;
+
+        tx.commit()?;
+
+        Ok(sessions)
+    }
+
+    fn trash(&self) -> Result<Vec<TrashItem>, Error> {
+        let mut stmt = self.conn.prepare("SELECT item FROM trash ORDER BY id")?;
+
+        let items = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        items
+            .iter()
+            .map(|item| serde_json::from_str(item).map_err(|_| corrupt("trash item", item)))
+            .collect()
+    }
+
+    fn delete_trashed(&self, id: u64) -> Result<bool, Error> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM trash WHERE id = ?1", params![id])?
+            > 0)
+    }
+
+    fn bowlers(&self) -> Result<Vec<Bowler>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT bowler FROM sessions ORDER BY bowler")?;
+
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(names.iter().map(|name| Bowler::build(name)).collect())
+    }
+
+    fn dates(&self, bowler: &Bowler) -> Result<Vec<Date>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT date FROM sessions WHERE bowler = ?1 ORDER BY date")?;
+
+        let days = stmt
+            .query_map(params![bowler.name()], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        days.iter()
+            .map(|day| parse_date_key(day).ok_or_else(|| corrupt("date", day)))
+            .collect()
+    }
+
+    fn drop_all(&self) -> Result<(), Error> {
+        self.conn.execute("DELETE FROM sessions", [])?;
+
+        Ok(())
+    }
+}
+
+// Writes the session row of `games` followed by each of its games
+fn insert_session(conn: &Connection, games: &Games) -> Result<(), Error> {
+    let key = games.key();
+    let session = games.session();
+
+    let (label, other_label) = encode_label(session.label());
+
+    conn.execute(
+        "INSERT INTO sessions (bowler, date, session, start, label, other_label, location, rules)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            key.bowler().name(),
+            date_key(key.date()),
+            key.session_id(),
+            session.start().map(|t| t.to_string()),
+            label,
+            other_label,
+            session.location(),
+            encode_rules(games.rules()),
+        ],
+    )?;
+
+    for game in games.games() {
+        insert_game(conn, key, game)?;
+    }
+
+    Ok(())
+}
+
+// Writes the game row of `game` with one row for each of its frames, markers and deadwood
+fn insert_game(conn: &Connection, key: &SessionKey, game: &Game) -> Result<(), Error> {
+    let bowler = key.bowler().name();
+    let date = date_key(key.date());
+    let session = key.session_id();
+    let game_num = game.game_num();
+
+    conn.execute(
+        "INSERT INTO games (bowler, date, session, game_num, variant, no_tap)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            bowler,
+            date,
+            session,
+            game_num,
+            encode_variant(game.variant()),
+            game.no_tap()
+        ],
+    )?;
+
+    for (frame_no, frame) in (1u8..).zip(game.frames()) {
+        let (kind, first, second, third) = encode_frame(frame);
+
+        conn.execute(
+            "INSERT INTO frames (bowler, date, session, game_num, frame_no, kind, first, second, third)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![bowler, date, session, game_num, frame_no, kind, first, second, third],
+        )?;
+    }
+
+    for marker in game.markers() {
+        conn.execute(
+            "INSERT INTO markers (bowler, date, session, game_num, frame_no, ball, marker)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                bowler,
+                date,
+                session,
+                game_num,
+                marker.frame(),
+                marker.ball(),
+                encode_marker(marker.marker())
+            ],
+        )?;
+    }
+
+    for deadwood in game.deadwood() {
+        conn.execute(
+            "INSERT INTO deadwood (bowler, date, session, game_num, frame_no, ball, pins)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                bowler,
+                date,
+                session,
+                game_num,
+                deadwood.frame(),
+                deadwood.ball(),
+                deadwood.pins()
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// One past the highest id in the trash
+fn next_trash_id(conn: &Connection) -> Result<u64, Error> {
+    let max = conn.query_row("SELECT MAX(id) FROM trash", [], |row| {
+        row.get::<_, Option<u64>>(0)
+    })?;
+
+    Ok(max.map_or(1, |max| max + 1))
+}
+
+// Items are kept whole as JSON, they are only ever read back as a whole
+fn insert_trashed(conn: &Connection, item: &TrashItem) -> Result<(), Error> {
+    let text = serde_json::to_string(item).map_err(io::Error::from)?;
+
+    conn.execute(
+        "INSERT INTO trash (id, item) VALUES (?1, ?2)",
+        params![item.id(), text],
+    )?;
+
+    Ok(())
+}
+
+// Removes a session along with its games, returning how many sessions were removed
+fn delete_session(conn: &Connection, key: &SessionKey) -> Result<usize, Error> {
+    Ok(conn.execute(
+        "DELETE FROM sessions WHERE bowler = ?1 AND date = ?2 AND session = ?3",
+        params![key.bowler().name(), date_key(key.date()), key.session_id()],
+    )?)
+}
+
+fn corrupt(column: &str, value: &str) -> Error {
+    Error::CorruptDataError(format!("unreadable {} `{}`", column, value))
+}
+
+fn decode_time(text: &str) -> Option<Time> {
+    let (hour, minute) = text.split_once(':')?;
+
+    Some(Time::build_with(hour.parse().ok()?, minute.parse().ok()?))
+}
+
+// Labels outside the fixed set are stored as `Other` with their text alongside
+fn encode_label(label: &SessionLabel) -> (&'static str, Option<&str>) {
+    match label {
+        SessionLabel::League => ("League", None),
+        SessionLabel::Practice => ("Practice", None),
+        SessionLabel::Tournament => ("Tournament", None),
+        SessionLabel::Other(other) => ("Other", Some(other)),
+    }
+}
+
+fn decode_label(label: &str, other_label: Option<String>) -> Option<SessionLabel> {
+    match label {
+        "League" => Some(SessionLabel::League),
+        "Practice" => Some(SessionLabel::Practice),
+        "Tournament" => Some(SessionLabel::Tournament),
+        "Other" => other_label.map(SessionLabel::Other),
+        _ => None,
+    }
+}
+
+fn encode_rules(rules: RuleSet) -> &'static str {
+    match rules {
+        RuleSet::Traditional => "Traditional",
+        RuleSet::CurrentFrame => "CurrentFrame",
+    }
+}
+
+fn decode_rules(text: &str) -> Option<RuleSet> {
+    match text {
+        "Traditional" => Some(RuleSet::Traditional),
+        "CurrentFrame" => Some(RuleSet::CurrentFrame),
+        _ => None,
+    }
+}
+
+fn encode_variant(variant: Variant) -> &'static str {
+    match variant {
+        Variant::TenPin => "TenPin",
+        Variant::Duckpin => "Duckpin",
+        Variant::Candlepin => "Candlepin",
+        Variant::FivePin => "FivePin",
+    }
+}
+
+fn decode_variant(text: &str) -> Option<Variant> {
+    match text {
+        "TenPin" => Some(Variant::TenPin),
+        "Duckpin" => Some(Variant::Duckpin),
+        "Candlepin" => Some(Variant::Candlepin),
+        "FivePin" => Some(Variant::FivePin),
+        _ => None,
+    }
+}
+
+fn encode_marker(marker: Marker) -> &'static str {
+    match marker {
+        Marker::Foul => "Foul",
+        Marker::Gutter => "Gutter",
+        Marker::Split => "Split",
+        Marker::Pocket => "Pocket",
+    }
+}
+
+fn decode_marker(text: &str) -> Option<Marker> {
+    match text {
+        "Foul" => Some(Marker::Foul),
+        "Gutter" => Some(Marker::Gutter),
+        "Split" => Some(Marker::Split),
+        "Pocket" => Some(Marker::Pocket),
+        _ => None,
+    }
+}
+
+type FrameRow = (&'static str, Option<u16>, Option<u16>, Option<u16>);
+
+// Pin counts are stored as they are and leaves as their bits
+fn encode_frame(frame: &Frame) -> FrameRow {
+    match frame {
+        Frame::Uninit => ("Uninit", None, None, None),
+        Frame::TwoFrame(b1, b2) => ("TwoFrame", Some(*b1 as u16), Some(*b2 as u16), None),
+        Frame::ThreeFrame(b1, b2, b3) => (
+            "ThreeFrame",
+            Some(*b1 as u16),
+            Some(*b2 as u16),
+            Some(*b3 as u16),
+        ),
+        Frame::TwoLeave(l1, l2) => ("TwoLeave", Some(l1.bits()), Some(l2.bits()), None),
+        Frame::ThreeLeave(l1, l2, l3) => (
+            "ThreeLeave",
+            Some(l1.bits()),
+            Some(l2.bits()),
+            Some(l3.bits()),
+        ),
+    }
+}
+
+fn decode_frame(
+    kind: &str,
+    first: Option<u16>,
+    second: Option<u16>,
+    third: Option<u16>,
+) -> Option<Frame> {
+    let count = |ball: Option<u16>| ball.and_then(|b| u8::try_from(b).ok());
+    let leave = |ball: Option<u16>| ball.and_then(Pins::from_bits);
+
+    match kind {
+        "Uninit" => Some(Frame::Uninit),
+        "TwoFrame" => Some(Frame::TwoFrame(count(first)?, count(second)?)),
+        "ThreeFrame" => Some(Frame::ThreeFrame(
+            count(first)?,
+            count(second)?,
+            count(third)?,
+        )),
+        "TwoLeave" => Some(Frame::TwoLeave(leave(first)?, leave(second)?)),
+        "ThreeLeave" => Some(Frame::ThreeLeave(
+            leave(first)?,
+            leave(second)?,
+            leave(third)?,
+        )),
+        _ => None,
+    }
+}
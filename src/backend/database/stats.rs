@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{Game, Games};
+
+// Sums the statistics of a set of games are worked out from, so a store can add them up itself
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameTotals {
+    games: u32,
+    score: u32,
+    strikes: u32,
+    strike_chances: u32,
+    spares: u32,
+    spare_chances: u32,
+    open_frames: u32,
+    first_ball_pins: u32,
+}
+
+impl GameTotals {
+    // Constructor
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    pub fn build_from_game(game: &Game, rules: RuleSet) -> Self {
+        Self {
+            games: 1,
+            score: game.score_with(rules) as u32,
+            strikes: game.num_strikes() as u32,
+            strike_chances: game.strike_chances() as u32,
+            spares: game.num_spares() as u32,
+            spare_chances: game.spare_chances() as u32,
+            open_frames: game.open_frames() as u32,
+            first_ball_pins: game.first_ball_pins() as u32,
+        }
+    }
+
+    // Only the games `Games` takes its statistics over
+    pub fn build_from_games(games: &Games) -> Self {
+        games
+            .games()
+            .iter()
+            .filter(|g| games.includes_no_tap() || !g.is_no_tap())
+            .fold(Self::default(), |acc, g| {
+                acc.add(&Self::build_from_game(g, games.rules()))
+            })
+    }
+
+    // Getter
+    pub fn games(&self) -> u32 {
+        self.games
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn strikes(&self) -> u32 {
+        self.strikes
+    }
+
+    pub fn spares(&self) -> u32 {
+        self.spares
+    }
+
+    pub fn open_frames(&self) -> u32 {
+        self.open_frames
+    }
+
+    // Method
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            games: self.games + other.games,
+            score: self.score + other.score,
+            strikes: self.strikes + other.strikes,
+            strike_chances: self.strike_chances + other.strike_chances,
+            spares: self.spares + other.spares,
+            spare_chances: self.spare_chances + other.spare_chances,
+            open_frames: self.open_frames + other.open_frames,
+            first_ball_pins: self.first_ball_pins + other.first_ball_pins,
+        }
+    }
+
+    pub fn average(&self) -> f32 {
+        self.score as f32 / self.games as f32
+    }
+
+    pub fn strike_rate(&self) -> f32 {
+        self.strikes as f32 / self.strike_chances as f32
+    }
+
+    pub fn spare_rate(&self) -> f32 {
+        self.spares as f32 / self.spare_chances as f32
+    }
+
+    pub fn open_frame_rate(&self) -> f32 {
+        self.open_frames as f32 / (self.games * 10) as f32
+    }
+
+    pub fn avg_first_ball_pinfall(&self) -> f32 {
+        self.first_ball_pins as f32 / (self.games * 10) as f32
+    }
+}
@@ -0,0 +1,156 @@
+use super::db_conn::GameStore;
+use crate::backend::core::types::{Bowler, Date, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+// What to do with a day the target already has games on
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    // Leave the day in the target as it is
+    #[default]
+    Skip,
+    // Add the games the target session is missing, matched by game number
+    Merge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncConflict {
+    // The target already has games on this day
+    Day(Bowler, Date),
+    // Both stores have this game number in the session but with different frames
+    Game(SessionKey, u8),
+}
+
+// Outcome of copying one store into another
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SyncReport {
+    copied: Vec<SessionKey>,
+    merged: Vec<(SessionKey, u8)>,
+    conflicts: Vec<SyncConflict>,
+    mismatches: Vec<(Bowler, Date)>,
+}
+
+impl SyncReport {
+    // Getter
+    // Sessions added to the target, keyed as they are stored there
+    pub fn copied(&self) -> &[SessionKey] {
+        &self.copied
+    }
+
+    // Games added to sessions the target already had
+    pub fn merged(&self) -> &[(SessionKey, u8)] {
+        &self.merged
+    }
+
+    pub fn conflicts(&self) -> &[SyncConflict] {
+        &self.conflicts
+    }
+
+    // Days whose game count or score total in the target differs from what was written
+    pub fn mismatches(&self) -> &[(Bowler, Date)] {
+        &self.mismatches
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+// Copies every session of `source` into `target` one day at a time, checking each written day
+pub fn sync(
+    source: &dyn GameStore,
+    target: &dyn GameStore,
+    policy: ConflictPolicy,
+) -> Result<SyncReport, Error> {
+    let mut report = SyncReport::default();
+
+    for bowler in source.bowlers()? {
+        for date in source.dates(&bowler)? {
+            let sessions = source.get_games(&bowler, date)?;
+            let existing = target.get_games(&bowler, date)?;
+
+            if sessions.is_empty() {
+                continue;
+            }
+
+            if !existing.is_empty() {
+                report
+                    .conflicts
+                    .push(SyncConflict::Day(bowler.clone(), date));
+
+                if policy == ConflictPolicy::Skip {
+                    continue;
+                }
+            }
+
+            let expected = write_day(target, &sessions, existing, &mut report)?;
+            let written = target.get_games(&bowler, date)?;
+
+            if day_totals(&written) != day_totals(&expected) {
+                report.mismatches.push((bowler.clone(), date));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// Writes the sessions of a day into the target, returning what the target should now hold
+fn write_day(
+    target: &dyn GameStore,
+    sessions: &[Games],
+    mut existing: Vec<Games>,
+    report: &mut SyncReport,
+) -> Result<Vec<Games>, Error> {
+    for games in sessions {
+        let stored = match existing
+            .iter_mut()
+            .find(|s| s.session_id() == games.session_id())
+        {
+            None => {
+                let session_id = target.add_games(games)?;
+                let key = SessionKey::build(games.bowler().clone(), games.date(), session_id);
+
+                let mut stored = games.clone();
+                stored.set_key(key.clone());
+                existing.push(stored);
+
+                report.copied.push(key);
+                continue;
+            }
+            Some(stored) => stored,
+        };
+
+        for game in games.games() {
+            match stored
+                .games()
+                .iter()
+                .find(|g| g.game_num() == game.game_num())
+            {
+                Some(other) if other != game => report
+                    .conflicts
+                    .push(SyncConflict::Game(stored.key().clone(), game.game_num())),
+                Some(_) => {}
+                None => {
+                    target.add_game(stored.key(), game)?;
+                    stored.add_game(game.clone());
+
+                    report.merged.push((stored.key().clone(), game.game_num()));
+                }
+            }
+        }
+    }
+
+    Ok(existing)
+}
+
+// Games and total score of a day
+fn day_totals(sessions: &[Games]) -> (usize, u32) {
+    sessions
+        .iter()
+        .flat_map(|s| {
+            s.games()
+                .iter()
+                .map(move |g| g.score_with(s.rules()) as u32)
+        })
+        .fold((0, 0), |(count, total), score| (count + 1, total + score))
+}
@@ -1,3 +1,29 @@
+mod async_store;
+mod conformance;
+mod file_conn;
+mod history;
+mod integrity;
+mod memory_conn;
 mod mongodb_conn;
+mod sqlite_conn;
+mod stats;
+mod sync;
+mod trash;
 
-use super::mongodb_conn::DatabaseConn;
+use super::async_store::{AsyncStore, QUERY_PAGE_SIZE};
+use super::db_conn::GameStore;
+use super::file_conn::FileConn;
+use super::history::{
+    redo_target, removed_session, undo_target, Change, ChangeKind, Operation, SessionChange,
+};
+use super::integrity::{check_games, renumbered, Issue, RepairMode};
+use super::memory_conn::MemoryConn;
+use super::mongodb_conn::{
+    check_document, migrate_document, redacted_uri, session_document, ConnectOptions, DatabaseConn,
+    MongoSettings,
+};
+use super::query::{GameQuery, SortOrder};
+use super::sqlite_conn::SqliteConn;
+use super::stats::GameTotals;
+use super::sync::{sync, ConflictPolicy, SyncConflict};
+use super::trash::{is_restored, numbered_for, TrashItem, DEFAULT_RETENTION};
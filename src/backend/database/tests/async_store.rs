@@ -0,0 +1,133 @@
+use std::sync::mpsc;
+
+use super::{conformance, AsyncStore, GameQuery, GameStore, MemoryConn, QUERY_PAGE_SIZE};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+use crate::error::CoreError;
+
+fn memory_store() -> AsyncStore {
+    AsyncStore::build(Box::new(MemoryConn::build()))
+}
+
+#[test]
+fn blocking_calls() {
+    let store = AsyncStore::open(|| Ok(Box::new(MemoryConn::build())))
+        .wait()
+        .unwrap();
+
+    conformance::check_store(&store);
+}
+
+#[tokio::test]
+async fn awaited_calls() {
+    let store = memory_store();
+    let bowler = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let games = Games::build_for(
+        SessionKey::build(bowler.clone(), date, 1),
+        vec!["X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap()],
+    );
+
+    assert_eq!(store.add_games_async(&games).await.unwrap(), 1);
+    assert_eq!(store.add_games_async(&games).await.unwrap(), 2);
+    assert_eq!(
+        store.bowlers_async().await.unwrap(),
+        std::slice::from_ref(&bowler)
+    );
+    assert_eq!(store.get_games_async(&bowler, date).await.unwrap().len(), 2);
+
+    let mut query = GameQuery::build();
+    query.set_page(1, 1);
+    let page = store.query_async(&query).await.unwrap();
+    assert_eq!(page.iter().map(Games::session_id).collect::<Vec<u8>>(), [2]);
+
+    assert!(store.validate_async().await.unwrap());
+    assert_eq!(store.integrity_report_async().await.unwrap().checked(), 2);
+    store.drop_all_async().await.unwrap();
+    assert!(store.bowlers_async().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn blocking_in_async() {
+    let store = memory_store();
+
+    // Waiting would block the runtime, so it fails instead of panicking
+    assert!(matches!(
+        store.bowlers(),
+        Err(CoreError::BlockingInAsyncError)
+    ));
+    assert!(matches!(
+        store.query(&GameQuery::build()).unwrap().next(),
+        Some(Err(CoreError::BlockingInAsyncError))
+    ));
+    assert!(store.bowlers_async().await.unwrap().is_empty());
+}
+
+#[test]
+fn paged_query() {
+    let store = memory_store();
+    let games = Games::build_for(
+        SessionKey::build(Bowler::build("Sam"), Date::build_with(2024, 2, 5), 1),
+        vec!["X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap()],
+    );
+
+    let total = QUERY_PAGE_SIZE * 2 + 50;
+    for _ in 0..total {
+        store.add_games(&games).unwrap();
+    }
+
+    let session_ids = |query: &GameQuery| {
+        store
+            .query(query)
+            .unwrap()
+            .map(|games| games.unwrap().session_id() as usize)
+            .collect::<Vec<usize>>()
+    };
+
+    // The blocking query goes through every page, the async one hands back a page at most
+    let mut query = GameQuery::build();
+    assert_eq!(session_ids(&query), (1..=total).collect::<Vec<usize>>());
+    assert_eq!(
+        store.query_async(&query).wait().unwrap().len(),
+        QUERY_PAGE_SIZE
+    );
+
+    query.set_skip(10);
+    query.set_limit(Some(QUERY_PAGE_SIZE + 20));
+    assert_eq!(
+        session_ids(&query),
+        (11..=QUERY_PAGE_SIZE + 30).collect::<Vec<usize>>()
+    );
+
+    query.set_limit(Some(0));
+    assert!(session_ids(&query).is_empty());
+}
+
+#[test]
+fn pending_jobs() {
+    let store = memory_store();
+    let (finish, finished) = mpsc::channel::<()>();
+
+    // Jobs run in order, so the second waits on the first
+    let mut first = store.run(move |_| {
+        finished.recv().unwrap();
+        Ok(1)
+    });
+    let mut second = store.run(|store| store.bowlers());
+
+    assert!(first.try_take().is_none());
+    assert!(second.try_take().is_none());
+
+    finish.send(()).unwrap();
+    assert_eq!(first.wait().unwrap(), 1);
+    assert!(second.wait().unwrap().is_empty());
+}
+
+#[test]
+fn open_fails() {
+    let opened = AsyncStore::open(|| Err(CoreError::MissingSettingError(String::from("DB_URI"))));
+
+    assert!(matches!(
+        opened.wait(),
+        Err(CoreError::MissingSettingError(name)) if name == "DB_URI"
+    ));
+}
@@ -0,0 +1,486 @@
+// Behaviour every storage backend has to share, run against an empty store
+
+use mongodb::bson::DateTime;
+
+use super::{GameQuery, GameStore, GameTotals, Issue, SortOrder, TrashItem};
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{
+    Bowler, Date, Game, Games, Session, SessionKey, SessionLabel, Time,
+};
+use crate::error::CoreError;
+
+// Two games of a league session, shared by the tests of every backend
+pub(super) fn sample_games(bowler: &Bowler, date: Date) -> Games {
+    let mut second = "9- 9- 9- 9- 9- 9- 9- 9- 9- 9-".parse::<Game>().unwrap();
+    *second.game_num_mut() = 2;
+
+    let mut games = Games::build_for(
+        SessionKey::build(bowler.clone(), date, 1),
+        vec!["X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap(), second],
+    );
+    games.set_session(Session::build_with(
+        Time::build_with(19, 30),
+        SessionLabel::League,
+        Some("Lucky Strike"),
+    ));
+
+    games
+}
+
+pub(super) fn assert_same(left: &Games, right: &Games) {
+    assert_eq!(left.key(), right.key());
+    assert_eq!(left.session(), right.session());
+    assert_eq!(left.rules(), right.rules());
+    assert_eq!(left.games(), right.games());
+}
+
+pub(super) fn check_store(store: &dyn GameStore) {
+    empty(store);
+    add_and_get(store);
+    duplicate(store);
+    modify(store);
+    remove(store);
+    validate(store);
+    query(store);
+    totals(store);
+    trash(store);
+}
+
+fn empty(store: &dyn GameStore) {
+    let bowler = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let key = SessionKey::build(bowler.clone(), date, 1);
+
+    assert!(store.bowlers().unwrap().is_empty());
+    assert!(store.get_games(&bowler, date).unwrap().is_empty());
+    assert!(store.get_session(&key).unwrap().is_none());
+    assert!(store.get_game(&key, 1).unwrap().is_none());
+    assert_eq!(store.num_games(&bowler, date).unwrap(), 0);
+    assert!(store.dates(&bowler).unwrap().is_empty());
+}
+
+fn add_and_get(store: &dyn GameStore) {
+    let sam = Bowler::build("Sam");
+    let alex = Bowler::build("Alex");
+    let date = Date::build_with(2024, 2, 5);
+    let games = sample_games(&sam, date);
+
+    // Each set of games becomes the next session of its day
+    assert_eq!(store.add_games(&games).unwrap(), 1);
+    assert_eq!(store.add_games(&games).unwrap(), 2);
+    assert_eq!(store.add_games(&sample_games(&alex, date)).unwrap(), 1);
+
+    assert_same(&store.get_session(games.key()).unwrap().unwrap(), &games);
+
+    let sessions = store.get_games(&sam, date).unwrap();
+    assert_eq!(
+        sessions.iter().map(Games::session_id).collect::<Vec<u8>>(),
+        [1, 2]
+    );
+    assert_eq!(store.num_games(&sam, date).unwrap(), 4);
+
+    let mut bowlers = store.bowlers().unwrap();
+    bowlers.sort_by(|l, r| l.name().cmp(r.name()));
+    assert_eq!(bowlers, [alex, sam.clone()]);
+
+    let earlier = Date::build_with(2023, 12, 30);
+    store.add_games(&sample_games(&sam, earlier)).unwrap();
+    assert_eq!(store.dates(&sam).unwrap(), [earlier, date]);
+
+    // Adding a game starts its session when the session is not stored yet
+    let key = SessionKey::build(sam.clone(), date, 3);
+    store.add_game(&key, &Game::build(1)).unwrap();
+    store.add_game(&key, &Game::build(2)).unwrap();
+
+    let started = store.get_session(&key).unwrap().unwrap();
+    assert_eq!(started.session(), &Session::default());
+    assert_eq!(started.games(), [Game::build(1), Game::build(2)]);
+    assert_eq!(store.get_game(&key, 2).unwrap(), Some(Game::build(2)));
+    assert!(store.get_game(&key, 3).unwrap().is_none());
+
+    store.drop_all().unwrap();
+    assert!(store.bowlers().unwrap().is_empty());
+}
+
+fn duplicate(store: &dyn GameStore) {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    store.add_games(&games).unwrap();
+
+    // A game number is only used once in a session, whichever way the game comes in
+    let key = games.key().clone();
+    assert!(matches!(
+        store.add_game(&key, &games.games()[0]),
+        Err(CoreError::DuplicateGameError(k, 1)) if k == key
+    ));
+    assert_same(&store.get_session(&key).unwrap().unwrap(), &games);
+
+    let mut twice = games.clone();
+    *twice.games_mut()[1].game_num_mut() = 1;
+
+    let next = SessionKey::build(key.bowler().clone(), key.date(), 2);
+    assert!(matches!(
+        store.add_games(&twice),
+        Err(CoreError::DuplicateGameError(k, 1)) if k == next
+    ));
+    assert!(matches!(
+        store.modify_games(&twice),
+        Err(CoreError::DuplicateGameError(k, 1)) if k == key
+    ));
+    assert_eq!(store.get_games(key.bowler(), key.date()).unwrap().len(), 1);
+    assert_same(&store.get_session(&key).unwrap().unwrap(), &games);
+
+    store.drop_all().unwrap();
+}
+
+fn modify(store: &dyn GameStore) {
+    let bowler = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let mut games = sample_games(&bowler, date);
+    store.add_games(&games).unwrap();
+
+    let game = "X X X X X X X X X XXX".parse::<Game>().unwrap();
+    store.modify_game(games.key(), &game).unwrap();
+    assert_eq!(store.get_game(games.key(), 1).unwrap(), Some(game.clone()));
+
+    // Nothing matched is an error
+    let mut unknown = game.clone();
+    *unknown.game_num_mut() = 5;
+    assert!(matches!(
+        store.modify_game(games.key(), &unknown),
+        Err(CoreError::GameNotFoundError(key, 5)) if &key == games.key()
+    ));
+
+    let missing = SessionKey::build(bowler.clone(), date, 4);
+    assert!(matches!(
+        store.modify_game(&missing, &game),
+        Err(CoreError::GameNotFoundError(key, 1)) if key == missing
+    ));
+
+    games.set_rules(RuleSet::CurrentFrame);
+    games.set_session(Session::default());
+    store.modify_games(&games).unwrap();
+    assert_same(&store.get_session(games.key()).unwrap().unwrap(), &games);
+
+    games.set_key(missing.clone());
+    assert!(matches!(
+        store.modify_games(&games),
+        Err(CoreError::SessionNotFoundError(key)) if key == missing
+    ));
+
+    store.drop_all().unwrap();
+}
+
+fn remove(store: &dyn GameStore) {
+    let sam = Bowler::build("Sam");
+    let alex = Bowler::build("Alex");
+    let date = Date::build_with(2024, 2, 5);
+    let next_day = Date::build_with(2024, 2, 6);
+    let games = sample_games(&sam, date);
+
+    store.add_games(&games).unwrap();
+    store.add_games(&games).unwrap();
+    store.add_games(&sample_games(&sam, next_day)).unwrap();
+    store.add_games(&sample_games(&alex, date)).unwrap();
+
+    assert_eq!(
+        store.remove_game(games.key(), 2).unwrap().as_ref(),
+        games.games().get(1)
+    );
+    assert!(store.remove_game(games.key(), 2).unwrap().is_none());
+    assert_eq!(store.num_games(&sam, date).unwrap(), 3);
+
+    let removed = store.remove_session(games.key()).unwrap().unwrap();
+    assert_eq!(removed.session_id(), 1);
+    assert_eq!(removed.games(), &games.games()[..1]);
+    assert!(store.remove_session(games.key()).unwrap().is_none());
+
+    // Only the day of the bowler asked for is removed
+    let removed = store.remove_games(&sam, date).unwrap();
+    assert_eq!(
+        removed.iter().map(Games::session_id).collect::<Vec<u8>>(),
+        [2]
+    );
+    assert!(store.get_games(&sam, date).unwrap().is_empty());
+    assert!(store.remove_games(&sam, date).unwrap().is_empty());
+    assert_eq!(store.num_games(&sam, next_day).unwrap(), 2);
+    assert_eq!(store.dates(&sam).unwrap(), [next_day]);
+    assert_eq!(store.num_games(&alex, date).unwrap(), 2);
+
+    store.drop_all().unwrap();
+    assert!(store.bowlers().unwrap().is_empty());
+    assert!(store.get_games(&alex, date).unwrap().is_empty());
+}
+
+fn validate(store: &dyn GameStore) {
+    let games = sample_games(&Bowler::default(), Date::build_with(2024, 2, 5));
+
+    assert!(store.validate());
+
+    store.add_games(&games).unwrap();
+    assert!(store.validate());
+
+    let report = store.integrity_report().unwrap();
+    assert_eq!(report.checked(), 1);
+    assert!(report.is_valid());
+
+    // An unplayed game is not a valid game
+    store.add_game(games.key(), &Game::build(3)).unwrap();
+    assert!(!store.validate());
+
+    let report = store.integrity_report().unwrap();
+    assert_eq!(report.invalid().len(), 1);
+    assert_eq!(report.invalid()[0].key(), Some(games.key()));
+    assert!(report.invalid()[0]
+        .issues()
+        .contains(&Issue::UnplayedFrame(3, 1)));
+
+    store.drop_all().unwrap();
+}
+
+fn query(store: &dyn GameStore) {
+    let sam = Bowler::build("Sam");
+    let alex = Bowler::build("Alex");
+    let january = Date::build_with(2024, 1, 10);
+    let february = Date::build_with(2024, 2, 5);
+    let march = Date::build_with(2024, 3, 1);
+
+    // A practice session of a single 90
+    let mut practice = sample_games(&sam, january);
+    practice.set_session(Session::default());
+    practice.remove_game(1);
+
+    store.add_games(&sample_games(&sam, march)).unwrap();
+    store.add_games(&sample_games(&sam, february)).unwrap();
+    store.add_games(&sample_games(&sam, february)).unwrap();
+    store.add_games(&sample_games(&alex, february)).unwrap();
+    store.add_games(&practice).unwrap();
+
+    let keys = |query: &GameQuery| {
+        store
+            .query(query)
+            .unwrap()
+            .map(|games| {
+                let games = games.unwrap();
+                (
+                    games.bowler().name().to_string(),
+                    games.date(),
+                    games.session_id(),
+                )
+            })
+            .collect::<Vec<(String, Date, u8)>>()
+    };
+    let key = |bowler: &Bowler, date, session_id| (bowler.name().to_string(), date, session_id);
+
+    // Days in order, then bowlers, then sessions
+    assert_eq!(
+        keys(&GameQuery::build()),
+        [
+            key(&sam, january, 1),
+            key(&alex, february, 1),
+            key(&sam, february, 1),
+            key(&sam, february, 2),
+            key(&sam, march, 1),
+        ]
+    );
+
+    let month = GameQuery::build_between(&sam, february, Date::build_with(2024, 2, 29));
+    assert_eq!(
+        keys(&month),
+        [key(&sam, february, 1), key(&sam, february, 2)]
+    );
+
+    let mut latest = GameQuery::build();
+    latest.set_order(SortOrder::Descending);
+    latest.set_page(0, 2);
+    assert_eq!(keys(&latest), [key(&sam, march, 1), key(&sam, february, 2)]);
+
+    latest.set_page(1, 2);
+    assert_eq!(
+        keys(&latest),
+        [key(&sam, february, 1), key(&alex, february, 1)]
+    );
+
+    latest.set_page(3, 2);
+    assert!(keys(&latest).is_empty());
+
+    latest.set_limit(Some(0));
+    assert!(keys(&latest).is_empty());
+
+    let mut labelled = GameQuery::build();
+    labelled.set_label(Some(SessionLabel::Practice));
+    assert_eq!(keys(&labelled), [key(&sam, january, 1)]);
+
+    // Paging counts only the sessions that have a game over the minimum
+    let mut scored = GameQuery::build();
+    scored.set_bowler(Some(&sam));
+    scored.set_min_score(Some(100));
+    scored.set_page(0, 1);
+    assert_eq!(keys(&scored), [key(&sam, february, 1)]);
+
+    scored.set_min_score(Some(250));
+    assert!(keys(&scored).is_empty());
+
+    store.drop_all().unwrap();
+}
+
+fn totals(store: &dyn GameStore) {
+    let sam = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let mut games = sample_games(&sam, date);
+    games.games_mut()[1].set_no_tap(Some(9));
+
+    assert_eq!(
+        store.totals(&GameQuery::build(), false).unwrap(),
+        GameTotals::build()
+    );
+
+    store.add_games(&games).unwrap();
+    store
+        .add_games(&sample_games(&Bowler::build("Alex"), date))
+        .unwrap();
+
+    let query = GameQuery::build_between(&sam, date, date);
+    assert_eq!(
+        store.totals(&query, false).unwrap(),
+        GameTotals::build_from_games(&games)
+    );
+
+    games.set_include_no_tap(true);
+    assert_eq!(
+        store.totals(&query, true).unwrap(),
+        GameTotals::build_from_games(&games)
+    );
+    assert_eq!(store.totals(&GameQuery::build(), true).unwrap().games(), 4);
+
+    // Picked and paged like `query` picks and pages sessions
+    let next_day = Date::build_with(2024, 2, 6);
+    let low = "9- 9- 9- 9- 9- 9- 9- 9- 9- 9-".parse::<Game>().unwrap();
+    let mut low = Games::build_for(SessionKey::build(sam.clone(), next_day, 1), vec![low]);
+    low.set_include_no_tap(true);
+    store.add_games(&low).unwrap();
+
+    let mut alex = sample_games(&Bowler::build("Alex"), date);
+    alex.set_include_no_tap(true);
+
+    let mut high = GameQuery::build();
+    high.set_min_score(Some(200));
+    assert_eq!(
+        store.totals(&high, true).unwrap(),
+        GameTotals::build_from_games(&alex).add(&GameTotals::build_from_games(&games))
+    );
+
+    // Alex, then Sam on the same day, then Sam on the next
+    let mut second = GameQuery::build();
+    second.set_page(1, 1);
+    assert_eq!(
+        store.totals(&second, true).unwrap(),
+        GameTotals::build_from_games(&games)
+    );
+
+    let mut latest = GameQuery::build();
+    latest.set_order(SortOrder::Descending);
+    latest.set_limit(Some(1));
+    assert_eq!(
+        store.totals(&latest, true).unwrap(),
+        GameTotals::build_from_games(&low)
+    );
+
+    store.drop_all().unwrap();
+}
+
+fn trash(store: &dyn GameStore) {
+    let bowler = Bowler::build("Robin");
+    let date = Date::build_with(2024, 3, 1);
+    let games = sample_games(&bowler, date);
+    let key = games.key().clone();
+
+    // Only what this check removed, the trash of a shared store may hold more
+    let latest = || {
+        store
+            .trash()
+            .unwrap()
+            .into_iter()
+            .filter(|item| item.key().bowler() == &bowler)
+            .collect::<Vec<TrashItem>>()
+            .pop()
+            .unwrap()
+    };
+
+    store.add_games(&games).unwrap();
+
+    let mut third = games.games()[0].clone();
+    *third.game_num_mut() = 3;
+    store.add_game(&key, &third).unwrap();
+
+    // A removed game comes back under its own number and in its place, and only once
+    store.remove_game(&key, 2).unwrap();
+    let item = latest();
+    assert_eq!(item.game(), games.games().get(1));
+    assert_eq!(store.restore_trashed(item.id()).unwrap(), Some(key.clone()));
+    assert_eq!(store.get_game(&key, 2).unwrap().as_ref(), item.game());
+    assert_eq!(
+        store
+            .get_session(&key)
+            .unwrap()
+            .unwrap()
+            .games()
+            .iter()
+            .map(Game::game_num)
+            .collect::<Vec<u8>>(),
+        [1, 2, 3]
+    );
+    assert!(store.validate());
+    assert!(store.trash().unwrap().iter().all(|i| i.id() != item.id()));
+    assert!(store.restore_trashed(item.id()).unwrap().is_none());
+
+    // Or after the other games when its number was taken since
+    let removed = store.remove_game(&key, 1).unwrap().unwrap();
+    store.add_game(&key, &removed).unwrap();
+    store.restore_trashed(latest().id()).unwrap();
+    let mut renumbered = removed.clone();
+    *renumbered.game_num_mut() = 4;
+    assert_eq!(store.get_game(&key, 4).unwrap(), Some(renumbered));
+
+    // A removed session comes back whole, after the sessions of its day when its key was taken
+    let session = store.remove_session(&key).unwrap().unwrap();
+    let item = latest();
+    assert!(item.game().is_none());
+    assert_eq!(store.restore_trashed(item.id()).unwrap(), Some(key.clone()));
+    assert_same(&store.get_session(&key).unwrap().unwrap(), &session);
+
+    store.remove_session(&key).unwrap();
+    assert_eq!(store.add_games(&games).unwrap(), 1);
+    let moved = store.restore_trashed(latest().id()).unwrap().unwrap();
+    assert_eq!(moved.session_id(), 2);
+    assert_eq!(
+        store.get_session(&moved).unwrap().unwrap().games(),
+        session.games()
+    );
+
+    // Every session of a removed day gets an item of its own
+    assert_eq!(store.remove_games(&bowler, date).unwrap().len(), 2);
+    let day = store
+        .trash()
+        .unwrap()
+        .into_iter()
+        .filter(|item| item.key().bowler() == &bowler)
+        .collect::<Vec<TrashItem>>();
+    assert_eq!(day.len(), 2);
+    assert!(store.restore_trashed(u64::MAX).unwrap().is_none());
+
+    for item in &day {
+        assert!(store.delete_trashed(item.id()).unwrap());
+        assert!(!store.delete_trashed(item.id()).unwrap());
+    }
+
+    // Nothing expired is left after a purge
+    store.purge_trash().unwrap();
+    let now = DateTime::now();
+    assert!(store
+        .trash()
+        .unwrap()
+        .iter()
+        .all(|item| !item.is_expired(now)));
+
+    store.drop_all().unwrap();
+}
@@ -0,0 +1,134 @@
+use std::{env, fs, path::PathBuf};
+
+use super::conformance::{self, sample_games};
+use super::{FileConn, GameStore, Issue};
+use crate::backend::core::types::{Bowler, Date};
+use crate::error::CoreError;
+
+// Fresh directory for each test so they can run side by side
+fn test_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("bowling-files-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    dir
+}
+
+#[test]
+fn conformance() {
+    let dir = test_dir("conformance");
+    let store = FileConn::open(&dir).unwrap();
+
+    conformance::check_store(&store);
+
+    drop(store);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn round_trip() {
+    let dir = test_dir("round-trip");
+    let store = FileConn::open(&dir).unwrap();
+    let bowler = Bowler::build("Sam O'Neil");
+    let date = Date::build_with(2024, 2, 5);
+    let games = sample_games(&bowler, date);
+
+    store.add_games(&games).unwrap();
+    store.add_games(&games).unwrap();
+
+    // Names are encoded for the file system and decoded when read back
+    assert_eq!(store.bowlers().unwrap(), [bowler]);
+
+    // One document for the day, and no temporary files left behind
+    let days = fs::read_dir(dir.join("Sam%20O%27Neil"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(days, ["2024-02-05.json"]);
+
+    drop(store);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn trash_kept() {
+    let dir = test_dir("trash");
+    let store = FileConn::open(&dir).unwrap();
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    store.add_games(&games).unwrap();
+    store.remove_session(games.key()).unwrap();
+
+    // The trash outlives the store and is not taken for a bowler
+    drop(store);
+    let store = FileConn::open(&dir).unwrap();
+    assert!(store.bowlers().unwrap().is_empty());
+
+    let item = store.trash().unwrap().pop().unwrap();
+    assert_eq!(item.games().games(), games.games());
+
+    store.drop_all().unwrap();
+    assert_eq!(store.trash().unwrap().len(), 1);
+
+    store.restore_trashed(item.id()).unwrap();
+    assert!(store.trash().unwrap().is_empty());
+    assert_eq!(
+        store.get_session(games.key()).unwrap().unwrap().games(),
+        games.games()
+    );
+
+    drop(store);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn lock() {
+    let dir = test_dir("lock");
+    let store = FileConn::open(&dir).unwrap();
+
+    // A second instance can not open the directory while the first holds it
+    assert!(matches!(
+        FileConn::open(&dir),
+        Err(CoreError::StoreLockedError(path)) if path == dir
+    ));
+
+    drop(store);
+    assert!(FileConn::open(&dir).is_ok());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn corrupt_day() {
+    let dir = test_dir("corrupt-day");
+    let store = FileConn::open(&dir).unwrap();
+    let bowler = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let next_day = Date::build_with(2024, 2, 6);
+
+    store.add_games(&sample_games(&bowler, date)).unwrap();
+    store.add_games(&sample_games(&bowler, next_day)).unwrap();
+
+    // Bowlers are kept in directories of their own, next to the lock and the trash
+    let path = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .flat_map(|bowler_dir| fs::read_dir(bowler_dir).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.ends_with("2024-02-06.json"))
+        .unwrap();
+    fs::write(&path, "[{\"_id\": ").unwrap();
+
+    // The day is reported and the rest still checked
+    let report = store.integrity_report().unwrap();
+    assert_eq!(report.checked(), 2);
+    assert_eq!(report.invalid().len(), 1);
+
+    let invalid = &report.invalid()[0];
+    assert_eq!(invalid.id(), path.display().to_string());
+    assert!(invalid.key().is_none());
+    assert!(matches!(invalid.issues(), [Issue::Undecodable(_)]));
+    assert!(!store.validate());
+
+    drop(store);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,102 @@
+use super::{
+    redo_target, removed_session, undo_target, Change, ChangeKind, Operation, SessionChange,
+};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+
+fn key(session_id: u8) -> SessionKey {
+    SessionKey::build(
+        Bowler::build("Sam"),
+        Date::build_with(2024, 2, 5),
+        session_id,
+    )
+}
+
+fn games(session_id: u8) -> Games {
+    Games::build_for(
+        key(session_id),
+        vec!["X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap()],
+    )
+}
+
+fn log(kinds: &[ChangeKind]) -> Vec<Change> {
+    kinds
+        .iter()
+        .zip(1..)
+        .map(|(kind, seq)| Change::build(seq, "sam", *kind, Vec::new()))
+        .collect()
+}
+
+fn seq(change: Option<&Change>) -> Option<u64> {
+    change.map(Change::seq)
+}
+
+const ADD: ChangeKind = ChangeKind::Edit(Operation::AddGames);
+
+#[test]
+fn undo() {
+    assert!(undo_target(&[]).is_none());
+    assert_eq!(seq(undo_target(&log(&[ADD, ADD]))), Some(2));
+
+    // Undone changes are skipped, undoing walks back through the log
+    let history = log(&[ADD, ADD, ChangeKind::Undo(2)]);
+    assert_eq!(seq(undo_target(&history)), Some(1));
+
+    let history = log(&[ADD, ADD, ChangeKind::Undo(2), ChangeKind::Undo(1)]);
+    assert!(undo_target(&history).is_none());
+
+    // A redo can be undone again
+    let history = log(&[ADD, ChangeKind::Undo(1), ChangeKind::Redo(2)]);
+    assert_eq!(seq(undo_target(&history)), Some(3));
+}
+
+#[test]
+fn redo() {
+    assert!(redo_target(&log(&[ADD, ADD])).is_none());
+
+    // The latest undo is redone first
+    let history = log(&[ADD, ADD, ChangeKind::Undo(2), ChangeKind::Undo(1)]);
+    assert_eq!(seq(redo_target(&history)), Some(4));
+
+    let history = log(&[
+        ADD,
+        ADD,
+        ChangeKind::Undo(2),
+        ChangeKind::Undo(1),
+        ChangeKind::Redo(4),
+    ]);
+    assert_eq!(seq(redo_target(&history)), Some(3));
+
+    // Changing anything after an undo leaves nothing to redo
+    let history = log(&[ADD, ChangeKind::Undo(1), ADD]);
+    assert!(redo_target(&history).is_none());
+}
+
+#[test]
+fn removed() {
+    let removed = Change::build(
+        2,
+        "sam",
+        ChangeKind::Edit(Operation::RemoveGames),
+        vec![
+            SessionChange::build(key(1), Some(games(1)), None),
+            SessionChange::build(key(2), Some(games(2)), None),
+        ],
+    );
+    let added = Change::build(
+        1,
+        "sam",
+        ADD,
+        vec![SessionChange::build(key(1), None, Some(games(1)))],
+    );
+    let history = [added, removed];
+
+    assert_eq!(
+        removed_session(&history, &key(2)).map(Games::key),
+        Some(&key(2))
+    );
+    assert!(removed_session(&history, &key(3)).is_none());
+
+    let reversed = history[1].sessions()[0].reversed();
+    assert!(reversed.before().is_none());
+    assert_eq!(reversed.after().map(Games::key), Some(&key(1)));
+}
@@ -0,0 +1,97 @@
+use super::{check_games, renumbered, Issue};
+use crate::backend::core::types::{Bowler, Date, Frame, Game, Games, Marker, SessionKey};
+
+fn session(games: Vec<Game>) -> Games {
+    Games::build_for(
+        SessionKey::build(Bowler::build("Sam"), Date::build_with(2024, 2, 5), 1),
+        games,
+    )
+}
+
+fn game(num: u8) -> Game {
+    let mut game = "X 9/ 8- X X X 7/ 9- X XXX".parse::<Game>().unwrap();
+    *game.game_num_mut() = num;
+
+    game
+}
+
+#[test]
+fn valid_session() {
+    assert!(check_games(&session(Vec::new())).is_empty());
+    assert!(check_games(&session(vec![game(1), game(2)])).is_empty());
+    assert!(renumbered(&session(vec![game(1), game(2)])).is_none());
+}
+
+#[test]
+fn numbering() {
+    assert_eq!(
+        check_games(&session(vec![game(1), game(3)])),
+        [Issue::GameOrder]
+    );
+    assert_eq!(
+        check_games(&session(vec![game(1), game(1)])),
+        [Issue::GameOrder, Issue::DuplicateGame(1)]
+    );
+
+    // Games are put in game number order, duplicates keep the order they were stored in
+    let mut first = game(2);
+    first.frames_mut()[0] = Frame::from((7, 2));
+    let fixed = renumbered(&session(vec![first.clone(), game(2), game(1)])).unwrap();
+
+    let mut expected = vec![game(1), first, game(3)];
+    *expected[1].game_num_mut() = 2;
+    assert_eq!(fixed.games(), expected);
+    assert!(check_games(&fixed).is_empty());
+}
+
+#[test]
+fn frames() {
+    let mut unplayed = game(1);
+    unplayed.frames_mut()[3] = Frame::build();
+
+    let mut invalid = game(2);
+    invalid.frames_mut()[0] = Frame::from((8, 5));
+    invalid.frames_mut()[9] = Frame::from((7, 2, 1));
+
+    assert_eq!(
+        check_games(&session(vec![unplayed, invalid.clone()])),
+        [
+            Issue::UnplayedFrame(1, 4),
+            Issue::InvalidFrame(2, 1),
+            Issue::InvalidFrame(2, 10)
+        ]
+    );
+
+    // Frames can not be fixed by renumbering
+    assert!(renumbered(&session(vec![game(1), game(1), invalid])).is_none());
+}
+
+#[test]
+fn invalid_game() {
+    // A foul on a strike and a no-tap count above the rack
+    let mut foul = game(1);
+    foul.set_marker(1, 1, Some(Marker::Foul));
+
+    let mut no_tap = game(2);
+    no_tap.set_no_tap(Some(11));
+
+    assert_eq!(
+        check_games(&session(vec![foul, no_tap])),
+        [Issue::InvalidGame(1), Issue::InvalidGame(2)]
+    );
+}
+
+#[test]
+fn no_tap() {
+    // A 9-pin no-tap 300, the nines only count as strikes once no-tap is applied
+    let mut frames = vec![Frame::from((9, 0)); 9];
+    frames.push(Frame::from((9, 9, 9)));
+
+    let mut perfect = Game::build_with(1, frames);
+    perfect.set_no_tap(Some(9));
+
+    let games = session(vec![perfect]);
+    assert!(games.games()[0].is_valid());
+    assert!(games.is_valid());
+    assert!(check_games(&games).is_empty());
+}
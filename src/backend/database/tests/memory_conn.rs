@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use super::{conformance, GameStore, MemoryConn};
+use crate::backend::core::types::{Bowler, Date, Game, Games};
+use crate::error::Error;
+
+#[test]
+fn conformance() {
+    conformance::check_store(&MemoryConn::build());
+}
+
+#[test]
+fn stores_copies() {
+    let store = MemoryConn::build();
+    let date = Date::build_with(2024, 2, 5);
+    let mut games = Games::build_with(date, Game::build(1));
+
+    store.add_games(&games).unwrap();
+
+    // Changing the games after storing them leaves the stored session alone
+    games.add_game(Game::build(2));
+    assert_eq!(store.num_games(&Bowler::default(), date).unwrap(), 1);
+}
+
+#[test]
+fn error_message() {
+    let store = MemoryConn::build();
+    let date = Date::build_with(2024, 2, 5);
+    let games = Games::build_with(date, Game::build(1));
+    store.add_games(&games).unwrap();
+
+    let mut missing = Game::build(1);
+    *missing.game_num_mut() = 3;
+
+    let err = store.modify_game(games.key(), &missing).unwrap_err();
+    assert_eq!(
+        Error::from(err).to_string(),
+        "[!] No game 3 in session 1 for Default on 2024/02/05"
+    );
+}
+
+#[test]
+fn purge_trash() {
+    let mut store = MemoryConn::build();
+    let date = Date::build_with(2024, 2, 5);
+    let games = Games::build_with(date, Game::build(1));
+    store.add_games(&games).unwrap();
+    store.add_games(&games).unwrap();
+
+    // Only what was removed while nothing was kept is purged
+    store.set_trash_retention(Duration::ZERO);
+    store.remove_game(games.key(), 1).unwrap();
+    store.set_trash_retention(Duration::from_secs(60));
+    store.remove_games(&Bowler::default(), date).unwrap();
+
+    assert_eq!(store.trash().unwrap().len(), 3);
+    assert_eq!(store.purge_trash().unwrap(), 1);
+    assert_eq!(store.trash().unwrap().len(), 2);
+    assert_eq!(store.purge_trash().unwrap(), 0);
+}
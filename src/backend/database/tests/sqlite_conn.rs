@@ -0,0 +1,129 @@
+use std::env;
+
+use super::conformance::{self, assert_same, sample_games};
+use super::{GameStore, Issue, SqliteConn};
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{
+    Bowler, Date, Frame, Game, Games, Marker, Pins, Session, SessionKey, SessionLabel, Time,
+};
+use crate::backend::core::variant::Variant;
+
+// The shared sample with a marker, a no-tap threshold, variants, deadwood and a label of its own,
+// which are all kept in columns of their own
+fn sample_columns(bowler: &Bowler, date: Date) -> Games {
+    let mut games = sample_games(bowler, date);
+
+    let first = &mut games.games_mut()[0];
+    first.set_marker(3, 2, Some(Marker::Gutter));
+    first.set_no_tap(Some(9));
+
+    games.add_game(Game::build_from_rolls_with(3, Variant::Duckpin, &[3.into(); 30]).unwrap());
+
+    let mut candlepin =
+        Game::build_from_rolls_with(4, Variant::Candlepin, &[3.into(); 30]).unwrap();
+    candlepin.set_deadwood(1, 2, 1);
+    games.add_game(candlepin);
+    games.set_rules(RuleSet::CurrentFrame);
+    games.set_session(Session::build_with(
+        Time::build_with(19, 30),
+        SessionLabel::Other(String::from("Scotch doubles")),
+        Some("Lucky Strike"),
+    ));
+
+    games
+}
+
+#[test]
+fn conformance() {
+    conformance::check_store(&SqliteConn::open_in_memory().unwrap());
+}
+
+#[test]
+fn round_trip() {
+    let store = SqliteConn::open_in_memory().unwrap();
+    let bowler = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let games = sample_columns(&bowler, date);
+
+    assert_eq!(store.add_games(&games).unwrap(), 1);
+
+    let stored = store.get_session(games.key()).unwrap().unwrap();
+    assert_same(&stored, &games);
+    assert_eq!(stored.games()[0].marker(3, 2), Some(Marker::Gutter));
+    assert_eq!(stored.games()[0].no_tap(), Some(9));
+    assert_eq!(stored.games()[2].variant(), Variant::Duckpin);
+    assert_eq!(stored.games()[3].deadwood_on(1, 2), 1);
+}
+
+#[test]
+fn leaves() {
+    let store = SqliteConn::open_in_memory().unwrap();
+    let key = SessionKey::build(Bowler::build("Sam"), Date::build_with(2024, 2, 5), 1);
+
+    let mut leave = Game::build(2);
+    leave.frames_mut()[0] = Frame::TwoLeave(Pins::build_with(&[7, 10]).unwrap(), Pins::NONE);
+
+    store.add_game(&key, &Game::build(1)).unwrap();
+    store.add_game(&key, &leave).unwrap();
+
+    let stored = store.get_session(&key).unwrap().unwrap();
+    assert_eq!(stored.session(), &Session::default());
+    assert_eq!(stored.games(), [Game::build(1), leave.clone()]);
+    assert_eq!(store.get_game(&key, 2).unwrap(), Some(leave));
+}
+
+#[test]
+fn persists_to_file() {
+    let path = env::temp_dir().join(format!("bowling-sqlite-{}.db", std::process::id()));
+    let games = sample_columns(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+
+    {
+        let store = SqliteConn::open(&path).unwrap();
+        store.add_games(&games).unwrap();
+    }
+
+    let store = SqliteConn::open(&path).unwrap();
+    assert_same(&store.get_session(games.key()).unwrap().unwrap(), &games);
+
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn corrupt_rows() {
+    let path = env::temp_dir().join(format!("bowling-sqlite-corrupt-{}.db", std::process::id()));
+    let bowler = Bowler::build("Sam");
+
+    {
+        let store = SqliteConn::open(&path).unwrap();
+        store
+            .add_games(&sample_games(&bowler, Date::build_with(2024, 2, 5)))
+            .unwrap();
+        store
+            .add_games(&sample_games(&bowler, Date::build_with(2024, 2, 6)))
+            .unwrap();
+    }
+
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE games SET variant = 'Bogus' WHERE date = '2024-02-06'",
+            [],
+        )
+        .unwrap();
+
+    // The day is reported and the rest still checked
+    let store = SqliteConn::open(&path).unwrap();
+    let report = store.integrity_report().unwrap();
+    assert_eq!(report.checked(), 2);
+    assert_eq!(report.invalid().len(), 1);
+    assert_eq!(report.invalid()[0].id(), "Sam 2024-02-06");
+    assert!(matches!(
+        report.invalid()[0].issues(),
+        [Issue::Undecodable(why)] if why.contains("Bogus")
+    ));
+    assert!(!store.validate());
+
+    drop(store);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,47 @@
+use super::conformance::sample_games;
+use super::GameTotals;
+use crate::backend::core::scoring::RuleSet;
+use crate::backend::core::types::{Bowler, Date, Games};
+
+pub(super) fn assert_agrees(totals: &GameTotals, games: &Games) {
+    assert_eq!(totals.average(), games.average());
+    assert_eq!(totals.strike_rate(), games.strike_rate());
+    assert_eq!(totals.spare_rate(), games.spare_rate());
+    assert_eq!(totals.open_frame_rate(), games.open_frame_rate());
+    // Summed per game in `Games`, so only the rounding can differ
+    assert!((totals.avg_first_ball_pinfall() - games.avg_first_ball_pinfall()).abs() < 1e-4);
+}
+
+#[test]
+fn game_totals() {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    let totals = GameTotals::build_from_game(&games.games()[0], RuleSet::default());
+
+    assert_eq!(totals.games(), 1);
+    assert_eq!(totals.score(), 211);
+    assert_eq!(totals.strikes(), 8);
+    assert_eq!(totals.spares(), 2);
+    assert_eq!(totals.open_frames(), 2);
+
+    assert_eq!(GameTotals::build(), GameTotals::default());
+    assert_eq!(totals.add(&GameTotals::build()), totals);
+}
+
+#[test]
+fn agrees_with_games() {
+    let mut games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    assert_agrees(&GameTotals::build_from_games(&games), &games);
+
+    games.set_rules(RuleSet::CurrentFrame);
+    assert_agrees(&GameTotals::build_from_games(&games), &games);
+
+    // No-tap games only count when asked for
+    games.set_rules(RuleSet::default());
+    games.games_mut()[0].set_no_tap(Some(9));
+    assert_eq!(GameTotals::build_from_games(&games).games(), 1);
+    assert_agrees(&GameTotals::build_from_games(&games), &games);
+
+    games.set_include_no_tap(true);
+    assert_eq!(GameTotals::build_from_games(&games).games(), 2);
+    assert_agrees(&GameTotals::build_from_games(&games), &games);
+}
@@ -0,0 +1,103 @@
+use super::{sync, ConflictPolicy, GameStore, MemoryConn, SqliteConn, SyncConflict};
+use crate::backend::core::types::{Bowler, Date, Game, Games, SessionKey};
+
+fn game(num: u8, notation: &str) -> Game {
+    let mut game = notation.parse::<Game>().unwrap();
+    *game.game_num_mut() = num;
+    game
+}
+
+fn day(bowler: &Bowler, date: Date, games: Vec<Game>) -> Games {
+    Games::build_for(SessionKey::build(bowler.clone(), date, 1), games)
+}
+
+#[test]
+fn copy_into_empty() {
+    let source = MemoryConn::build();
+    let target = SqliteConn::open_in_memory().unwrap();
+    let sam = Bowler::build("Sam");
+    let alex = Bowler::build("Alex");
+    let date = Date::build_with(2024, 2, 5);
+
+    source
+        .add_games(&day(&sam, date, vec![game(1, "X X X X X X X X X XXX")]))
+        .unwrap();
+    source
+        .add_games(&day(
+            &sam,
+            date,
+            vec![game(1, "9- 9- 9- 9- 9- 9- 9- 9- 9- 9-")],
+        ))
+        .unwrap();
+    source
+        .add_games(&day(
+            &alex,
+            Date::build_with(2024, 3, 1),
+            vec![game(1, "X X X X X X X X X XXX")],
+        ))
+        .unwrap();
+
+    let report = sync(&source, &target, ConflictPolicy::Skip).unwrap();
+
+    assert_eq!(report.copied().len(), 3);
+    assert!(report.conflicts().is_empty());
+    assert!(report.is_verified());
+
+    let copied = target.get_games(&sam, date).unwrap();
+    assert_eq!(copied.len(), 2);
+    assert_eq!(copied[1].games()[0].score(), 90);
+    assert_eq!(target.dates(&alex).unwrap(), [Date::build_with(2024, 3, 1)]);
+}
+
+#[test]
+fn conflicts() {
+    let source = MemoryConn::build();
+    let target = MemoryConn::build();
+    let sam = Bowler::build("Sam");
+    let date = Date::build_with(2024, 2, 5);
+    let key = SessionKey::build(sam.clone(), date, 1);
+
+    source
+        .add_games(&day(
+            &sam,
+            date,
+            vec![
+                game(1, "X X X X X X X X X XXX"),
+                game(2, "9- 9- 9- 9- 9- 9- 9- 9- 9- 9-"),
+                game(3, "9/ 9/ 9/ 9/ 9/ 9/ 9/ 9/ 9/ 9/9"),
+            ],
+        ))
+        .unwrap();
+    target
+        .add_games(&day(
+            &sam,
+            date,
+            vec![
+                game(1, "X X X X X X X X X XXX"),
+                game(2, "8- 8- 8- 8- 8- 8- 8- 8- 8- 8-"),
+            ],
+        ))
+        .unwrap();
+
+    // Skipping leaves the target day alone
+    let report = sync(&source, &target, ConflictPolicy::Skip).unwrap();
+    assert_eq!(report.conflicts(), [SyncConflict::Day(sam.clone(), date)]);
+    assert!(report.merged().is_empty());
+    assert_eq!(target.num_games(&sam, date).unwrap(), 2);
+
+    // Merging adds the missing game and keeps the target's copy of a differing one
+    let report = sync(&source, &target, ConflictPolicy::Merge).unwrap();
+    assert_eq!(
+        report.conflicts(),
+        [
+            SyncConflict::Day(sam.clone(), date),
+            SyncConflict::Game(key.clone(), 2)
+        ]
+    );
+    assert_eq!(report.merged(), [(key.clone(), 3)]);
+    assert!(report.is_verified());
+
+    let merged = target.get_session(&key).unwrap().unwrap();
+    assert_eq!(merged.games().len(), 3);
+    assert_eq!(merged.games()[1].score(), 80);
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use mongodb::bson::DateTime;
+
+use super::conformance::sample_games;
+use super::{is_restored, numbered_for, TrashItem, DEFAULT_RETENTION};
+use crate::backend::core::types::{Bowler, Date};
+
+#[test]
+fn build_session() {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    let item = TrashItem::build_session(1, &games, DEFAULT_RETENTION);
+
+    assert_eq!(item.id(), 1);
+    assert_eq!(item.key(), games.key());
+    assert_eq!(item.games().games(), games.games());
+    assert!(item.game().is_none());
+
+    let kept = item.expires_at().timestamp_millis() - item.removed_at().timestamp_millis();
+    assert_eq!(kept, DEFAULT_RETENTION.as_millis() as i64);
+}
+
+#[test]
+fn build_game() {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    let game = &games.games()[1];
+    let item = TrashItem::build_game(2, &games, game, DEFAULT_RETENTION);
+
+    // Only the removed game is kept, along with what it needs to go back
+    assert_eq!(item.key(), games.key());
+    assert_eq!(item.games().games(), std::slice::from_ref(game));
+    assert_eq!(item.games().session(), games.session());
+    assert_eq!(item.games().rules(), games.rules());
+    assert_eq!(item.game(), Some(game));
+}
+
+#[test]
+fn is_expired() {
+    let item = TrashItem::build_session(
+        1,
+        &sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5)),
+        Duration::from_secs(60),
+    );
+    let removed_at = item.removed_at().timestamp_millis();
+
+    assert!(!item.is_expired(item.removed_at()));
+    assert!(!item.is_expired(DateTime::from_millis(removed_at + 59_000)));
+    assert!(item.is_expired(DateTime::from_millis(removed_at + 60_000)));
+
+    let gone = TrashItem::build_session(
+        2,
+        &sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5)),
+        Duration::ZERO,
+    );
+    assert!(gone.is_expired(gone.removed_at()));
+}
+
+#[test]
+fn renumbered() {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    let mut game = games.games()[0].clone();
+
+    // A game keeps its number unless the session has a game under it
+    *game.game_num_mut() = 7;
+    assert_eq!(numbered_for(&game, &games).game_num(), 7);
+
+    *game.game_num_mut() = 1;
+    assert_eq!(
+        numbered_for(&game, &games).game_num() as usize,
+        games.games().len() + 1
+    );
+}
+
+#[test]
+fn restored() {
+    let games = sample_games(&Bowler::build("Sam"), Date::build_with(2024, 2, 5));
+    let session = TrashItem::build_session(1, &games, DEFAULT_RETENTION);
+    let game = TrashItem::build_game(2, &games, &games.games()[0], DEFAULT_RETENTION);
+
+    assert!(is_restored(&session, &games));
+    assert!(is_restored(&game, &games));
+
+    let mut without = games.clone();
+    without.remove_game(1);
+    assert!(!is_restored(&session, &without));
+    assert!(!is_restored(&game, &without));
+}
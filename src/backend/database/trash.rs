@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use mongodb::bson::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::db_conn::GameStore;
+use crate::backend::core::types::{Game, Games, SessionKey};
+use crate::error::CoreError as Error;
+
+// How long removed games are kept before a purge removes them for good
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// A removed session, or a single game removed from one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    #[serde(rename = "_id")]
+    id: u64,
+    removed_at: DateTime,
+    expires_at: DateTime,
+    // Only the removed game when a single game was removed
+    games: Games,
+    game_num: Option<u8>,
+}
+
+impl TrashItem {
+    // Constructor
+    pub fn build_session(id: u64, games: &Games, retention: Duration) -> Self {
+        let removed_at = DateTime::now();
+
+        Self {
+            id,
+            removed_at,
+            expires_at: DateTime::from_millis(
+                removed_at.timestamp_millis() + retention.as_millis() as i64,
+            ),
+            games: games.clone(),
+            game_num: None,
+        }
+    }
+
+    // `games` is the session the game was removed from, its other games are not kept
+    pub fn build_game(id: u64, games: &Games, game: &Game, retention: Duration) -> Self {
+        let mut removed = Games::build_for(games.key().clone(), vec![game.clone()]);
+        removed.set_session(games.session().clone());
+        removed.set_rules(games.rules());
+
+        Self {
+            game_num: Some(game.game_num()),
+            ..Self::build_session(id, &removed, retention)
+        }
+    }
+
+    // Getter
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn key(&self) -> &SessionKey {
+        self.games.key()
+    }
+
+    pub fn games(&self) -> &Games {
+        &self.games
+    }
+
+    // The game when only a game was removed
+    pub fn game(&self) -> Option<&Game> {
+        self.game_num
+            .and_then(|num| self.games.games().iter().find(|g| g.game_num() == num))
+    }
+
+    pub fn removed_at(&self) -> DateTime {
+        self.removed_at
+    }
+
+    pub fn expires_at(&self) -> DateTime {
+        self.expires_at
+    }
+
+    // Method
+    pub fn is_expired(&self, now: DateTime) -> bool {
+        self.expires_at <= now
+    }
+}
+
+// Id after the highest of `items`, trash items are numbered from 1
+pub(crate) fn next_id(items: &[TrashItem]) -> u64 {
+    items.iter().map(TrashItem::id).max().unwrap_or(0) + 1
+}
+
+// Puts the games of `item` back in `store`, returning the session they went to. A game goes back
+// into its session, and a session under its own key unless that was taken since
+pub(crate) fn put_back<S: GameStore + ?Sized>(
+    store: &S,
+    item: &TrashItem,
+) -> Result<SessionKey, Error> {
+    let key = item.key();
+    let stored = store.get_session(key)?;
+
+    if let (Some(game), Some(stored)) = (item.game(), &stored) {
+        store.add_game(key, &numbered_for(game, stored))?;
+        return Ok(key.clone());
+    }
+
+    match (stored, item.games().games().first()) {
+        // Adding a game starts the session, its details and other games come back with it after
+        (None, Some(first)) => {
+            store.add_game(key, first)?;
+            store.modify_games(item.games())?;
+
+            Ok(key.clone())
+        }
+        _ => {
+            let session_id = store.add_games(item.games())?;
+
+            Ok(SessionKey::build(
+                key.bowler().clone(),
+                key.date(),
+                session_id,
+            ))
+        }
+    }
+}
+
+// `game` numbered after the games of `session` when its number was taken there since
+pub(crate) fn numbered_for(game: &Game, session: &Games) -> Game {
+    let mut game = game.clone();
+    let taken = session.games().iter().map(Game::game_num);
+
+    if taken.clone().any(|num| num == game.game_num()) {
+        *game.game_num_mut() = taken.max().unwrap_or(0) + 1;
+    }
+
+    game
+}
+
+// Whether `item` holds what was just brought back into `session` some other way, such as an undo
+pub(crate) fn is_restored(item: &TrashItem, session: &Games) -> bool {
+    if item.key() != session.key() {
+        return false;
+    }
+
+    match item.game() {
+        Some(game) => session.games().contains(game),
+        None => item.games().games() == session.games(),
+    }
+}
@@ -2,7 +2,7 @@ use std::io::stdin;
 
 use chrono::NaiveDate;
 
-use crate::backend::core::types::Date;
+use crate::backend::core::types::{Date, Frame};
 
 #[derive(Debug)]
 pub struct MenuOption {
@@ -78,31 +78,24 @@ pub fn parse_options(input_str: &str) -> MenuOption {
     }
 }
 
-// Parses &str into a vector of scores, if anything is not a number or greater than 10, return None
+// Parses &str into a vector of scores, either whitespace separated numbers or standard notation
+// ("X", "9/", "8-"), if anything is not a number or greater than 10, return None
 // Guarantees that the output vector is full of possible scores, values between 0-10
 pub fn parse_scores(input_str: &str) -> Option<Vec<u8>> {
-    let temp: Vec<String> = input_str
+    let parsed: Result<Vec<u8>, _> = input_str
         .split_whitespace()
-        .map(|s| s.to_string())
+        .map(|s| s.parse::<u8>())
         .collect();
 
-    let parsed = temp.iter().map(|s| s.parse::<u8>());
-
-    let mut scores = Vec::new();
-    for score in parsed {
-        if score.is_err() {
-            // Not a number
-            return None;
-        } else {
-            let score = score.unwrap();
-
-            if score > 10 {
-                // Not a valid score
-                return None;
-            }
+    let mut scores = match parsed {
+        Ok(scores) => scores,
+        // Not a number, try standard notation
+        Err(_) => input_str.parse::<Frame>().ok()?.balls(),
+    };
 
-            scores.push(score);
-        }
+    if scores.iter().any(|score| *score > 10) {
+        // Not a valid score
+        return None;
     }
 
     // If input_str represents a strike '10'
@@ -1 +1,20 @@
+use super::helper::parse_scores;
 
+#[test]
+fn parse_scores_numbers() {
+    assert_eq!(parse_scores("9 1"), Some(vec![9, 1]));
+    assert_eq!(parse_scores("10"), Some(vec![10, 0]));
+    assert_eq!(parse_scores("10 10 10"), Some(vec![10, 10, 10]));
+    assert_eq!(parse_scores("11 0"), None);
+    assert_eq!(parse_scores("a 0"), None);
+}
+
+#[test]
+fn parse_scores_notation() {
+    assert_eq!(parse_scores("9/"), Some(vec![9, 1]));
+    assert_eq!(parse_scores("X"), Some(vec![10, 0]));
+    assert_eq!(parse_scores("8-"), Some(vec![8, 0]));
+    assert_eq!(parse_scores("XXX"), Some(vec![10, 10, 10]));
+    assert_eq!(parse_scores("X 9/"), Some(vec![10, 9, 1]));
+    assert_eq!(parse_scores("/9"), None);
+}
@@ -0,0 +1,302 @@
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use serde::Deserialize;
+
+use crate::backend::core::types::Bowler;
+use crate::backend::database::db_conn::GameStore;
+use crate::backend::database::file_conn::FileConn;
+use crate::backend::database::memory_conn::MemoryConn;
+use crate::backend::database::mongodb_conn::{
+    redacted_uri, ConnectOptions, DatabaseConn, MongoSettings,
+};
+use crate::backend::database::sqlite_conn::SqliteConn;
+use crate::error::CoreError as Error;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    MongoDB,
+    SQLite,
+    File,
+    Memory,
+}
+
+impl std::str::FromStr for Backend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mongodb" => Ok(Self::MongoDB),
+            "sqlite" => Ok(Self::SQLite),
+            "file" => Ok(Self::File),
+            "memory" => Ok(Self::Memory),
+            _ => Err(Error::InvalidSettingError(format!(
+                "unknown backend `{}`",
+                s
+            ))),
+        }
+    }
+}
+
+// Either `uri` or the separate fields, `uri` wins when both are set
+#[derive(Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MongoConfig {
+    uri: Option<String>,
+    user: Option<String>,
+    pass: Option<String>,
+    host: Option<String>,
+    port: Option<String>,
+    auth_db: Option<String>,
+    database: Option<String>,
+    collection: Option<String>,
+    timeout_ms: Option<u64>,
+    retries: Option<u8>,
+    // Bowler the documents from before there were bowlers are moved to on connecting
+    migrate_to: Option<String>,
+}
+
+impl MongoConfig {
+    // Fields set in `over` replace those set here
+    fn merge(self, over: Self) -> Self {
+        Self {
+            uri: over.uri.or(self.uri),
+            user: over.user.or(self.user),
+            pass: over.pass.or(self.pass),
+            host: over.host.or(self.host),
+            port: over.port.or(self.port),
+            auth_db: over.auth_db.or(self.auth_db),
+            database: over.database.or(self.database),
+            collection: over.collection.or(self.collection),
+            timeout_ms: over.timeout_ms.or(self.timeout_ms),
+            retries: over.retries.or(self.retries),
+            migrate_to: over.migrate_to.or(self.migrate_to),
+        }
+    }
+}
+
+// Passwords are left out so a config can be logged
+impl std::fmt::Debug for MongoConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MongoConfig")
+            .field("uri", &self.uri.as_deref().map(redacted_uri))
+            .field("user", &self.user)
+            .field("pass", &self.pass.as_ref().map(|_| "***"))
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("auth_db", &self.auth_db)
+            .field("database", &self.database)
+            .field("collection", &self.collection)
+            .field("timeout_ms", &self.timeout_ms)
+            .field("retries", &self.retries)
+            .field("migrate_to", &self.migrate_to)
+            .finish()
+    }
+}
+
+// Where the games are stored, read from the config file, then env vars, then flags
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    backend: Option<Backend>,
+    mongodb: MongoConfig,
+    sqlite_file: Option<PathBuf>,
+    file_dir: Option<PathBuf>,
+}
+
+impl Config {
+    // Constructor
+    // Layers `$XDG_CONFIG_HOME/bowling-tracker/config.toml` (or `--config`), env vars and `args`
+    pub fn load(args: &[String]) -> Result<Self, Error> {
+        let flags = Self::build_from_args(args)?;
+
+        // A missing `--config` file is an error, a missing default file is not
+        let path = match flag_value(args, "config")? {
+            Some(path) if !PathBuf::from(&path).is_file() => {
+                return Err(Error::InvalidSettingError(format!(
+                    "config file `{}` not found",
+                    path
+                )))
+            }
+            Some(path) => Some(PathBuf::from(path)),
+            None => Self::default_path().filter(|path| path.is_file()),
+        };
+
+        let file = match path {
+            Some(path) => Self::build_from_toml(&fs::read_to_string(path)?)?,
+            None => Self::default(),
+        };
+
+        let vars = Self::build_from_vars(|name| env::var(name).ok())?;
+
+        Ok(file.merge(vars).merge(flags))
+    }
+
+    pub fn build_from_toml(text: &str) -> Result<Self, Error> {
+        toml::from_str(text).map_err(|err| Error::InvalidSettingError(err.message().to_string()))
+    }
+
+    // DB_BACKEND, DB_FILE, DB_DIR, DB_URI, USER_NAME, USER_PASS, USER_AUTH, DB_HOST, DB_PORT,
+    // DB_NAME, COLL_NAME, DB_TIMEOUT_MS, DB_RETRIES and DB_MIGRATE_TO
+    pub fn build_from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        Ok(Self {
+            backend: var("DB_BACKEND").map(|b| b.parse()).transpose()?,
+            mongodb: MongoConfig {
+                uri: var("DB_URI"),
+                user: var("USER_NAME"),
+                pass: var("USER_PASS"),
+                host: var("DB_HOST"),
+                port: var("DB_PORT"),
+                auth_db: var("USER_AUTH"),
+                database: var("DB_NAME"),
+                collection: var("COLL_NAME"),
+                timeout_ms: parse_number("DB_TIMEOUT_MS", var("DB_TIMEOUT_MS"))?,
+                retries: parse_number("DB_RETRIES", var("DB_RETRIES"))?,
+                migrate_to: var("DB_MIGRATE_TO"),
+            },
+            sqlite_file: var("DB_FILE").map(PathBuf::from),
+            file_dir: var("DB_DIR").map(PathBuf::from),
+        })
+    }
+
+    // --backend, --uri, --db, --coll, --migrate-to, --db-file and --db-dir, as `--flag value` or
+    // `--flag=value`
+    pub fn build_from_args(args: &[String]) -> Result<Self, Error> {
+        let flag = |name| flag_value(args, name);
+
+        Ok(Self {
+            backend: flag("backend")?.map(|b| b.parse()).transpose()?,
+            mongodb: MongoConfig {
+                uri: flag("uri")?,
+                database: flag("db")?,
+                collection: flag("coll")?,
+                migrate_to: flag("migrate-to")?,
+                ..MongoConfig::default()
+            },
+            sqlite_file: flag("db-file")?.map(PathBuf::from),
+            file_dir: flag("db-dir")?.map(PathBuf::from),
+        })
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("bowling-tracker").join("config.toml"))
+    }
+
+    // Getter
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_default()
+    }
+
+    pub fn mongo_settings(&self) -> Result<MongoSettings, Error> {
+        let mongo = &self.mongodb;
+
+        let mut settings = match (&mongo.uri, &mongo.host) {
+            (Some(uri), _) => MongoSettings::build_from_uri(uri),
+            (None, Some(host)) => {
+                let port = mongo.port.as_deref().unwrap_or("27017");
+
+                match (&mongo.user, &mongo.pass) {
+                    (Some(user), Some(pass)) => MongoSettings::build(
+                        user,
+                        pass,
+                        host,
+                        port,
+                        mongo.auth_db.as_deref().unwrap_or("admin"),
+                    ),
+                    // Local servers without authentication
+                    _ => MongoSettings::build_from_uri(&format!("mongodb://{host}:{port}")),
+                }
+            }
+            (None, None) => {
+                return Err(Error::MissingSettingError(String::from(
+                    "mongodb.uri or mongodb.host",
+                )))
+            }
+        };
+
+        let defaults = ConnectOptions::build();
+        settings.set_options(ConnectOptions::build_with(
+            mongo
+                .timeout_ms
+                .map_or(defaults.timeout(), Duration::from_millis),
+            mongo.retries.unwrap_or(defaults.retries()),
+            defaults.backoff(),
+        ));
+        settings.set_database(mongo.database.as_deref());
+        settings.set_collection(mongo.collection.as_deref());
+
+        Ok(settings)
+    }
+
+    // Method
+    // Settings in `over` replace those set here
+    pub fn merge(self, over: Self) -> Self {
+        Self {
+            backend: over.backend.or(self.backend),
+            mongodb: self.mongodb.merge(over.mongodb),
+            sqlite_file: over.sqlite_file.or(self.sqlite_file),
+            file_dir: over.file_dir.or(self.file_dir),
+        }
+    }
+
+    pub fn connect(&self) -> Result<Box<dyn GameStore>, Error> {
+        let missing = |name: &str| Error::MissingSettingError(name.to_string());
+
+        Ok(match self.backend() {
+            Backend::MongoDB => {
+                let conn = DatabaseConn::connect_with(&self.mongo_settings()?)?;
+
+                // Sessions are only found by bowler, so older documents are moved before any reads
+                if let Some(bowler) = &self.mongodb.migrate_to {
+                    conn.migrate(&Bowler::build(bowler))?;
+                }
+
+                Box::new(conn)
+            }
+            Backend::SQLite => Box::new(SqliteConn::open(
+                self.sqlite_file
+                    .as_ref()
+                    .ok_or_else(|| missing("sqlite_file"))?,
+            )?),
+            Backend::File => Box::new(FileConn::open(
+                self.file_dir.as_ref().ok_or_else(|| missing("file_dir"))?,
+            )?),
+            Backend::Memory => Box::new(MemoryConn::build()),
+        })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(
+    name: &str,
+    value: Option<String>,
+) -> Result<Option<T>, Error> {
+    value
+        .map(|v| {
+            v.parse()
+                .map_err(|_| Error::InvalidSettingError(format!("{} is not a number", name)))
+        })
+        .transpose()
+}
+
+// Value of `--name value` or `--name=value`, the last one given wins
+fn flag_value(args: &[String], name: &str) -> Result<Option<String>, Error> {
+    let flag = format!("--{}", name);
+    let mut value = None;
+
+    for (idx, arg) in args.iter().enumerate() {
+        if let Some(inline) = arg.strip_prefix(&format!("{}=", flag)) {
+            value = Some(inline.to_string());
+        } else if *arg == flag {
+            value =
+                Some(args.get(idx + 1).cloned().ok_or_else(|| {
+                    Error::InvalidSettingError(format!("{} needs a value", flag))
+                })?);
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests;
@@ -1,20 +1,130 @@
-// TODO: Refactor: Errors
+use std::path::PathBuf;
+
+use crate::backend::core::types::SessionKey;
 
 // [!] General Error: General Errors, such as incorrect input, etc. (used for user errors)
 // [!!] Functional Error: Particular Function of Program Doesn't Work (used for internal errors)
 // [!!!] Crucial Error: Program Termination (used for severe internal errors)
 
-// TODO: use mongodb::error::Error as an example for this
+// Stage of connecting to MongoDB that failed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnectStep {
+    // The connection string itself, before anything is looked up
+    Parse,
+    // Looking up the host and getting an answer from it
+    Resolve,
+    Authenticate,
+    ListDatabases,
+    ListCollections,
+    AccessCollection,
+}
 
-pub const DATABASE_NOT_ASSIGNED_ERROR: &str = "DatabaseNotAssignedError";
-pub const COLLECTION_NOT_ASSIGNED_ERROR: &str = "CollectionNotAssignedError";
-pub const ELEMENT_NOT_FOUND_ERROR: &str = "ElementNotFoundEror";
+impl std::fmt::Display for ConnectStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "reading the connection string"),
+            Self::Resolve => write!(f, "reaching the server"),
+            Self::Authenticate => write!(f, "authenticating"),
+            Self::ListDatabases => write!(f, "listing databases"),
+            Self::ListCollections => write!(f, "listing collections"),
+            Self::AccessCollection => write!(f, "accessing the collection"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub enum CoreError {
-    BaseError,
-    DBConnError,
+    // Connecting
+    MissingSettingError(String),
+    InvalidSettingError(String),
+    ConnectionError(ConnectStep, mongodb::error::Error),
+    // Connection state
+    DatabaseNotAssignedError,
+    CollectionNotAssignedError,
+    SameDatabaseError(String),
+    // The credentials can not reach this database or collection
+    AccessDeniedError(String),
+    // Another running instance holds the store
+    StoreLockedError(PathBuf),
+    // Lookups
+    SessionNotFoundError(SessionKey),
+    GameNotFoundError(SessionKey, u8),
+    // Game numbers are unique within a session
+    DuplicateGameError(SessionKey, u8),
+    // Stored data that does not decode into games
+    CorruptDataError(String),
+    // The thread running the store for async callers is gone
+    StoreStoppedError,
+    // A blocking call on the async store made from async code, which has to await instead
+    BlockingInAsyncError,
+    // Backends
     MongoDBError(mongodb::error::Error),
+    SQLiteError(rusqlite::Error),
+    IOError(std::io::Error),
+}
+
+impl std::fmt::Display for CoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSettingError(name) => write!(f, "Setting {} is missing", name),
+            Self::InvalidSettingError(why) => write!(f, "Invalid setting: {}", why),
+            Self::ConnectionError(step, err) => {
+                write!(f, "Can not connect to MongoDB while {}: {}", step, err)
+            }
+            Self::DatabaseNotAssignedError => write!(f, "No database is selected"),
+            Self::CollectionNotAssignedError => write!(f, "No collection is selected"),
+            Self::SameDatabaseError(name) => write!(f, "Database {} is already selected", name),
+            Self::AccessDeniedError(name) => write!(f, "Access to {} was denied", name),
+            Self::StoreLockedError(path) => {
+                write!(f, "{} is in use by another instance", path.display())
+            }
+            Self::SessionNotFoundError(key) => write!(
+                f,
+                "No session {} for {} on {}",
+                key.session_id(),
+                key.bowler(),
+                key.date()
+            ),
+            Self::GameNotFoundError(key, game_num) => write!(
+                f,
+                "No game {} in session {} for {} on {}",
+                game_num,
+                key.session_id(),
+                key.bowler(),
+                key.date()
+            ),
+            Self::DuplicateGameError(key, game_num) => write!(
+                f,
+                "Game {} is already in session {} for {} on {}",
+                game_num,
+                key.session_id(),
+                key.bowler(),
+                key.date()
+            ),
+            Self::CorruptDataError(what) => write!(f, "Stored data is corrupt: {}", what),
+            Self::StoreStoppedError => write!(f, "The store is no longer running"),
+            Self::BlockingInAsyncError => {
+                write!(
+                    f,
+                    "Can not wait on the store from async code, await it instead"
+                )
+            }
+            Self::MongoDBError(err) => write!(f, "MongoDB: {}", err),
+            Self::SQLiteError(err) => write!(f, "SQLite: {}", err),
+            Self::IOError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ConnectionError(_, err) | Self::MongoDBError(err) => Some(err),
+            Self::SQLiteError(err) => Some(err),
+            Self::IOError(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl From<mongodb::error::Error> for CoreError {
@@ -23,12 +133,51 @@ impl From<mongodb::error::Error> for CoreError {
     }
 }
 
+impl From<std::io::Error> for CoreError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IOError(value)
+    }
+}
+
+impl From<rusqlite::Error> for CoreError {
+    fn from(value: rusqlite::Error) -> Self {
+        Self::SQLiteError(value)
+    }
+}
+
 pub enum Error {
     Info(String),    // [!]
     Warning(String), // [!!]
     Severe(String),  // [!!!]
 }
 
+// Lookups the user asked for are info, a store that can not be used a warning, and backend failures severe
+impl From<CoreError> for Error {
+    fn from(value: CoreError) -> Self {
+        let message = value.to_string();
+
+        match value {
+            CoreError::SameDatabaseError(_)
+            | CoreError::SessionNotFoundError(_)
+            | CoreError::GameNotFoundError(_, _)
+            | CoreError::DuplicateGameError(_, _) => Self::Info(message),
+            CoreError::MissingSettingError(_)
+            | CoreError::InvalidSettingError(_)
+            | CoreError::DatabaseNotAssignedError
+            | CoreError::CollectionNotAssignedError
+            | CoreError::AccessDeniedError(_)
+            | CoreError::StoreLockedError(_)
+            | CoreError::CorruptDataError(_) => Self::Warning(message),
+            CoreError::ConnectionError(_, _)
+            | CoreError::StoreStoppedError
+            | CoreError::BlockingInAsyncError
+            | CoreError::MongoDBError(_)
+            | CoreError::SQLiteError(_)
+            | CoreError::IOError(_) => Self::Severe(message),
+        }
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -2,71 +2,41 @@
 pub use std::env;
 
 pub mod backend;
+pub mod config;
 pub mod error;
 pub mod loops;
 
 pub mod app;
 
 pub mod prelude {
+    pub use crate::backend::core::scoring::*;
     pub use crate::backend::core::types::*;
+    pub use crate::backend::core::variant::*;
+    pub use crate::backend::database::db_conn::GameStore;
 
     // Add this as a feature, to allow for using different DB Connectors?
     // pub use crate::backend::database::mongodb_conn::DatabaseConn;
 }
 
-pub fn connect_db() -> Option<backend::database::mongodb_conn::DatabaseConn> {
-    let user_string = match env::var("USER_NAME") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-    let pass_string = match env::var("USER_PASS") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-    let auth_string = match env::var("USER_AUTH") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-
-    let host_string = match env::var("DB_HOST") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-    let port_string = match env::var("DB_PORT") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-
-    let db_string = match env::var("DB_NAME") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
-    };
-    let coll_string = match env::var("COLL_NAME") {
-        Ok(string) => string,
-        Err(_) => {
-            return None;
-        }
+// Storage backends, callers should hold them as a `GameStore`
+pub mod storage {
+    pub use crate::backend::database::async_store::{AsyncStore, Pending, QUERY_PAGE_SIZE};
+    pub use crate::backend::database::db_conn::GameStore;
+    pub use crate::backend::database::file_conn::FileConn;
+    pub use crate::backend::database::history::{Change, ChangeKind, Operation, SessionChange};
+    pub use crate::backend::database::integrity::{
+        IntegrityReport, InvalidSession, Issue, RepairMode,
     };
+    pub use crate::backend::database::memory_conn::MemoryConn;
+    pub use crate::backend::database::mongodb_conn::{ConnectOptions, DatabaseConn, MongoSettings};
+    pub use crate::backend::database::query::{GameQuery, GamesIter, SortOrder};
+    pub use crate::backend::database::sqlite_conn::SqliteConn;
+    pub use crate::backend::database::stats::GameTotals;
+    pub use crate::backend::database::sync::{sync, ConflictPolicy, SyncConflict, SyncReport};
+    pub use crate::backend::database::trash::TrashItem;
+}
 
-    Some(backend::database::mongodb_conn::DatabaseConn::connect_full(
-        &user_string,
-        &pass_string,
-        &host_string,
-        &port_string,
-        &auth_string,
-        &db_string,
-        &coll_string,
-    ))
+// Store picked by the config file, env vars and command line flags, see `config::Config`
+pub fn connect_db() -> Result<Box<dyn backend::database::db_conn::GameStore>, error::CoreError> {
+    config::Config::load(&env::args().skip(1).collect::<Vec<String>>())?.connect()
 }
@@ -24,7 +24,12 @@ pub fn new_game_loop(date: Date, game_num: u8) -> Option<Game> {
     let mut done = false;
 
     'frame_loop: loop {
-        println!("{}", new_game);
+        println!("{}", new_game.scorecard());
+        println!(
+            "Max Possible: {}\tGuaranteed: {}",
+            new_game.possible_score(),
+            new_game.guaranteed_score()
+        );
 
         if done {
             println!("\nGame Completed: Use `d` to save and exit game")
@@ -222,7 +227,7 @@ pub fn mod_game_loop(date: Date, game_num: u8, game: &mut Game) -> Option<&Game>
     let mut modified = false;
 
     loop {
-        println!("{}", game);
+        println!("{}", game.scorecard());
         println!("d: save changes and exit");
         println!("q: exit without saving");
 
@@ -1,10 +1,13 @@
 use dotenvy::dotenv;
 
 use bowling_interface::app::tui;
+use bowling_interface::storage::AsyncStore;
 
 fn main() {
-    dotenv().expect(".env not found");
+    // The .env file is optional, settings can also come from the environment or a config file
+    dotenv().ok();
 
-    let mut app = tui::App::default();
+    // Connecting happens in the background while the TUI is drawn
+    let mut app = tui::App::build_with(AsyncStore::open(bowling_interface::connect_db));
     let _res = tui::run(&mut app);
 }